@@ -0,0 +1,69 @@
+use aoc_2023_01::matcher::Matcher;
+use aoc_2023_01::part2::{extract_code_with, Vocabulary};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+/// Lines shaped like a puzzle input: runs of random letters around digits and number words.
+fn input() -> Vec<String> {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let mut state: u64 = 0x2545f4914f6cdd1d;
+    let mut random = move |n: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize % n
+    };
+
+    (0..100_000)
+        .map(|_| {
+            let mut line = String::new();
+
+            for _ in 0..3 + random(6) {
+                match random(5) {
+                    0 | 1 => line.push_str(words[random(words.len())]),
+                    2 => line.push(char::from(b'1' + random(9) as u8)),
+                    _ => {
+                        line.extend((0..1 + random(8)).map(|_| char::from(b'a' + random(26) as u8)))
+                    }
+                }
+            }
+
+            line
+        })
+        .collect()
+}
+
+fn extract(c: &mut Criterion) {
+    let lines = input();
+    let vocabulary = Vocabulary::english();
+    let matcher = Matcher::new(&vocabulary);
+
+    let mut group = c.benchmark_group("extract_code");
+    group.throughput(Throughput::Bytes(
+        lines.iter().map(|l| l.len() as u64).sum(),
+    ));
+    group.sample_size(10);
+
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            lines
+                .iter()
+                .filter_map(|l| extract_code_with(l, &vocabulary))
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("automaton", |b| {
+        b.iter(|| {
+            lines
+                .iter()
+                .filter_map(|l| matcher.extract_code(l))
+                .sum::<u32>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, extract);
+criterion_main!(benches);
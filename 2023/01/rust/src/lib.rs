@@ -0,0 +1,112 @@
+pub mod matcher;
+pub mod part1;
+pub mod part2;
+
+use aoc_common::{read_lines, Error, Solution};
+use matcher::Matcher;
+use std::io::Read;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        read_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        sum_codes(input, part1::extract_code)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        sum_codes(input, part2::extract_code)
+    }
+}
+
+fn sum_codes(lines: &[String], extract_code: fn(String) -> Option<u32>) -> Result<u32, Error> {
+    let mut sum = 0;
+    let mut missing = vec![];
+
+    for (i, line) in lines.iter().enumerate() {
+        match extract_code(line.clone()) {
+            Some(code) => sum += code,
+            None => missing.push(no_code(i, line)),
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(sum)
+    } else {
+        Err(Error::Solve(format!(
+            "No code on {} of {} lines\n{}",
+            missing.len(),
+            lines.len(),
+            missing.join("\n")
+        )))
+    }
+}
+
+/// Describes how the code of every line comes about, or only the lines without one when
+/// `only_missing` is set.
+pub fn explain(lines: &[String], matcher: &Matcher, only_missing: bool) -> Vec<String> {
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| match matcher.find(line) {
+            Some(_) if only_missing => None,
+            Some(calibration) => Some(format!("line {}: {calibration}", i + 1)),
+            None => Some(no_code(i, line)),
+        })
+        .collect()
+}
+
+fn no_code(i: usize, line: &str) -> String {
+    format!("line {}: no code in {line:?}", i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part2::Vocabulary;
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn lists_lines_without_code() {
+        let e = Day01::part1(&lines("1abc2\nabc\ntreb7uchet\n\n")).unwrap_err();
+
+        assert_eq!(
+            "Could not solve puzzle: No code on 2 of 4 lines\n\
+             line 2: no code in \"abc\"\n\
+             line 4: no code in \"\"",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn explain_lines() {
+        let matcher = Matcher::new(&Vocabulary::english());
+        let input = lines("eightwo\nxyz\n7");
+
+        assert_eq!(
+            vec![
+                "line 1: 82 from \"eight\" at 0..5 and \"two\" at 4..7",
+                "line 2: no code in \"xyz\"",
+                "line 3: 77 from \"7\" at 0..1 and \"7\" at 0..1",
+            ],
+            explain(&input, &matcher, false)
+        );
+        assert_eq!(
+            vec!["line 2: no code in \"xyz\""],
+            explain(&input, &matcher, true)
+        );
+    }
+}
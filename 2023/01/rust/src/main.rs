@@ -1,42 +1,47 @@
-mod part1;
-mod part2;
-
+use aoc_2023_01::matcher::Matcher;
+use aoc_2023_01::part2::Vocabulary;
+use aoc_2023_01::{explain, Day01};
+use aoc_common::cli::Args;
+use aoc_common::{open, read_lines, Error, Part};
 use std::env;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-// use part1::extract_code;
-use part2::extract_code;
+use std::process::ExitCode;
+
+/// Besides the shared options, `--explain` shows how every line's code comes about and
+/// `--missing` lists only the lines without one, for part 2 unless given `--part 1`.
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
 
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
+    let Some(i) = args
+        .iter()
+        .position(|a| a == "--explain" || a == "--missing")
+    else {
+        return aoc_common::cli::run::<Day01>();
+    };
+    let only_missing = args.remove(i) == "--missing";
 
-    if args.len() < 2 {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "No input file path provided",
-        ));
+    match diagnose(&args, only_missing) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
     }
+}
 
-    let path = Path::new(&args[1]);
-    let file = File::open(path)?;
-    let reader = io::BufReader::new(file);
+fn diagnose(args: &[String], only_missing: bool) -> Result<(), Error> {
+    let args = Args::parse(args)?;
+    let path = args.resolve_path(2023, 1)?;
 
-    let sum: Option<u32> = reader
-        .lines()
-        .map(|line| match line {
-            Ok(l) => extract_code(l),
-            Err(_) => None,
-        })
-        .sum();
+    // part 1 only knows digits
+    let vocabulary = match args.parts[..] {
+        [Part::One] => Vocabulary::default(),
+        _ => Vocabulary::english(),
+    };
 
-    if sum.is_none() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "Could not extract code from all lines",
-        ));
+    let lines = read_lines(open(&path)?)?;
+    for line in explain(&lines, &Matcher::new(&vocabulary), only_missing) {
+        println!("{line}");
     }
 
-    println!("The sum of all calibration values is {}", sum.unwrap());
     Ok(())
 }
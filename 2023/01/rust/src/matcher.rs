@@ -0,0 +1,240 @@
+//! Aho-Corasick automaton over digits and number words, compiled into a dense table so that every
+//! byte of a line costs a single lookup.
+
+use crate::part2::{leading_digit, Vocabulary};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// Length in bytes and value of a pattern that ends in a state.
+type Output = Option<(usize, u32)>;
+
+/// Digit or word found in a line, along with its byte span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match<'a> {
+    pub span: Range<usize>,
+    pub token: &'a str,
+    pub value: u32,
+}
+
+impl<'a> Match<'a> {
+    fn new(input: &'a str, (start, len, value): (usize, usize, u32)) -> Self {
+        Match {
+            span: start..start + len,
+            token: &input[start..start + len],
+            value,
+        }
+    }
+}
+
+impl Display for Match<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} at {:?}", self.token, self.span)
+    }
+}
+
+/// First and last match of a line, which make up its code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration<'a> {
+    pub first: Match<'a>,
+    pub last: Match<'a>,
+}
+
+impl Calibration<'_> {
+    pub fn code(&self) -> u32 {
+        10 * leading_digit(self.first.value) + self.last.value % 10
+    }
+}
+
+impl Display for Calibration<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} from {} and {}", self.code(), self.first, self.last)
+    }
+}
+
+/// Finds the first and last digit or word of a line in one pass over its bytes, with the same
+/// result as [`extract_code_with`](crate::part2::extract_code_with).
+pub struct Matcher {
+    /// Next state for every state and byte, with failure links already followed.
+    transitions: Vec<u32>,
+    /// Longest and shortest pattern ending in each state, which start earliest and latest.
+    outputs: Vec<(Output, Output)>,
+}
+
+impl Matcher {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let digits: Vec<_> = (0..10).map(|d| (d.to_string(), d)).collect();
+        let patterns = digits
+            .iter()
+            .map(|(d, v)| (d.as_str(), *v))
+            .chain(vocabulary.words());
+
+        // a trie first, where 0 marks a missing edge as nothing leads back to the root
+        let mut transitions = vec![0; 256];
+        let mut outputs: Vec<Output> = vec![None];
+
+        for (word, value) in patterns {
+            let mut state = 0;
+
+            for &b in word.as_bytes() {
+                let edge = state * 256 + b as usize;
+
+                if transitions[edge] == 0 {
+                    transitions[edge] = outputs.len() as u32;
+                    transitions.extend([0; 256]);
+                    outputs.push(None);
+                }
+
+                state = transitions[edge] as usize;
+            }
+
+            // like the scan, the first of several equal words wins
+            outputs[state].get_or_insert((word.len(), value));
+        }
+
+        let mut longest = outputs.clone();
+        let mut shortest = outputs;
+        let mut fail = vec![0; longest.len()];
+        let mut queue: VecDeque<usize> = transitions[..256]
+            .iter()
+            .map(|&s| s as usize)
+            .filter(|&s| s != 0)
+            .collect();
+
+        // breadth first, so the failure state of every state is complete before it is needed
+        while let Some(state) = queue.pop_front() {
+            let f = fail[state];
+
+            // patterns ending in the failure state are suffixes of the ones ending here
+            if longest[state].is_none() {
+                longest[state] = longest[f];
+            }
+            if shortest[f].is_some() {
+                shortest[state] = shortest[f];
+            }
+
+            for b in 0..256 {
+                let next = transitions[state * 256 + b] as usize;
+
+                if next == 0 {
+                    transitions[state * 256 + b] = transitions[f * 256 + b];
+                } else {
+                    fail[next] = transitions[f * 256 + b] as usize;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Matcher {
+            transitions,
+            outputs: longest.into_iter().zip(shortest).collect(),
+        }
+    }
+
+    pub fn extract_code(&self, input: &str) -> Option<u32> {
+        self.find(input).map(|c| c.code())
+    }
+
+    pub fn find<'a>(&self, input: &'a str) -> Option<Calibration<'a>> {
+        let mut state = 0;
+        let mut first: Option<(usize, usize, u32)> = None;
+        let mut last: Option<(usize, usize, u32)> = None;
+
+        for (end, &b) in input.as_bytes().iter().enumerate() {
+            state = self.transitions[state * 256 + b as usize] as usize;
+
+            let (longest, shortest) = self.outputs[state];
+
+            // a later match with the same start is a longer word, which wins for both ends
+            if let Some((len, value)) = longest {
+                let start = end + 1 - len;
+                if first.is_none_or(|(s, _, _)| start <= s) {
+                    first = Some((start, len, value));
+                }
+            }
+            if let Some((len, value)) = shortest {
+                let start = end + 1 - len;
+                if last.is_none_or(|(s, _, _)| start >= s) {
+                    last = Some((start, len, value));
+                }
+            }
+        }
+
+        Some(Calibration {
+            first: Match::new(input, first?),
+            last: Match::new(input, last?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part2::extract_code_with;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("two1nine")]
+    #[case("eightwothree")]
+    #[case("xtwone3four")]
+    #[case("sevenineteen")]
+    #[case("nineteenine")]
+    #[case("ninineteen")]
+    #[case("eleventwelven")]
+    #[case("seventeenineleven")]
+    #[case("é7ü")]
+    #[case("nothing")]
+    #[case("")]
+    fn agrees_with_scan(#[case] input: &str) {
+        let english = Vocabulary::english();
+        let words = Vocabulary::new(english.words().chain([
+            ("eleven", 11),
+            ("twelve", 12),
+            ("seventeen", 17),
+            ("nineteen", 19),
+        ]));
+
+        for vocabulary in [english, words] {
+            assert_eq!(
+                extract_code_with(input, &vocabulary),
+                Matcher::new(&vocabulary).extract_code(input),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn unicode_words() {
+        let matcher = Matcher::new(&Vocabulary::new([("drei", 3), ("fünf", 5)]));
+
+        assert_eq!(Some(53), matcher.extract_code("xfünfdrei"));
+        assert_eq!(None, matcher.extract_code("funf"));
+    }
+
+    #[test]
+    fn spans() {
+        let matcher = Matcher::new(&Vocabulary::english());
+        let calibration = matcher.find("xtwone3four").unwrap();
+
+        assert_eq!(
+            Match {
+                span: 1..4,
+                token: "two",
+                value: 2
+            },
+            calibration.first
+        );
+        assert_eq!(
+            Match {
+                span: 7..11,
+                token: "four",
+                value: 4
+            },
+            calibration.last
+        );
+        assert_eq!(
+            "24 from \"two\" at 1..4 and \"four\" at 7..11",
+            calibration.to_string()
+        );
+    }
+}
@@ -1,50 +1,93 @@
+use crate::matcher::Matcher;
 use lazy_static::lazy_static;
-use std::collections::HashMap;
+use std::cmp::Reverse;
 
-lazy_static! {
-    static ref NUMBERS: HashMap<&'static str, u32> = {
-        let mut m = HashMap::new();
-        m.insert("zero", 0);
-        m.insert("one", 1);
-        m.insert("two", 2);
-        m.insert("three", 3);
-        m.insert("four", 4);
-        m.insert("five", 5);
-        m.insert("six", 6);
-        m.insert("seven", 7);
-        m.insert("eight", 8);
-        m.insert("nine", 9);
-        m
-    };
+/// Words that spell out numbers, recognised alongside plain digits.
+///
+/// A word stands for the decimal digits of its value, so with multi-digit words like "eleven" the
+/// first match contributes its leading digit and the last match its trailing one.
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Empty words are left out, as they would match everywhere.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, u32)>) -> Self {
+        let mut words: Vec<(String, u32)> = words
+            .into_iter()
+            .map(|(w, v)| (w.into(), v))
+            .filter(|(w, _)| !w.is_empty())
+            .collect();
+
+        // longest first, so that "seventeen" wins over "seven" at the same position
+        words.sort_by_key(|(w, _)| Reverse(w.len()));
+
+        Vocabulary { words }
+    }
+
+    pub fn english() -> Self {
+        Self::new([
+            ("zero", 0),
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ])
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words.iter().map(|(w, v)| (w.as_str(), *v))
+    }
+
+    /// Value of the digit or word starting at byte `pos` of `input`.
+    fn value_at(&self, input: &str, pos: usize) -> Option<u32> {
+        let rest = &input[pos..];
+
+        rest.chars().next()?.to_digit(10).or_else(|| {
+            self.words
+                .iter()
+                .find(|(w, _)| rest.starts_with(w.as_str()))
+                .map(|&(_, v)| v)
+        })
+    }
 }
 
-fn to_digit(input: &str, pos: usize) -> Option<u32> {
-    let sub: String = input.chars().skip(pos).take(5).collect();
-    NUMBERS.keys().find_map(|k| {
-        if sub.starts_with(k) {
-            Some(NUMBERS[k])
-        } else {
-            None
-        }
-    })
+lazy_static! {
+    static ref ENGLISH: Matcher = Matcher::new(&Vocabulary::english());
 }
 
 pub fn extract_code(input: String) -> Option<u32> {
-    let chars: Vec<(usize, char)> = input.chars().enumerate().collect();
+    ENGLISH.extract_code(&input)
+}
 
-    let first: Option<u32> = chars
-        .iter()
-        .find_map(|(i, c)| c.to_digit(10).or(to_digit(&input, *i)));
+/// Combines the first and last digit or word of `input`. Words may overlap, so "eightwo" starts
+/// with an 8 and ends with a 2.
+///
+/// Scans from both ends and tries every word at every position, which [`Matcher`] avoids.
+pub fn extract_code_with(input: &str, vocabulary: &Vocabulary) -> Option<u32> {
+    let positions = input.char_indices().map(|(i, _)| i);
 
-    let last: Option<u32> = chars
-        .iter()
+    let first = positions
+        .clone()
+        .find_map(|i| vocabulary.value_at(input, i))?;
+    let last = positions
         .rev()
-        .find_map(|(i, c)| c.to_digit(10).or(to_digit(&input, *i)));
+        .find_map(|i| vocabulary.value_at(input, i))?;
+
+    Some(10 * leading_digit(first) + last % 10)
+}
 
-    match (first, last) {
-        (Some(f), Some(l)) => Some(10 * f + l),
-        _ => None,
+pub(crate) fn leading_digit(mut n: u32) -> u32 {
+    while n >= 10 {
+        n /= 10;
     }
+    n
 }
 
 #[cfg(test)]
@@ -63,4 +106,53 @@ mod tests {
     fn examples(#[case] input: String, #[case] expected: u32) {
         assert_eq!(Some(expected), extract_code(input));
     }
+
+    #[rstest]
+    #[case("zwei1neun", 29)]
+    #[case("achtzwei", 82)]
+    #[case("xdreix", 33)]
+    #[case("einsiebenx", 17)]
+    fn other_language(#[case] input: &str, #[case] expected: u32) {
+        let german = Vocabulary::new([
+            ("eins", 1),
+            ("zwei", 2),
+            ("drei", 3),
+            ("sieben", 7),
+            ("acht", 8),
+            ("neun", 9),
+        ]);
+
+        assert_eq!(Some(expected), extract_code_with(input, &german));
+    }
+
+    #[rstest]
+    #[case("eleven5twelve", 12)]
+    #[case("ten", 10)]
+    #[case("seventeen", 17)]
+    #[case("3seven", 37)]
+    #[case("nineteenine", 19)]
+    fn multi_digit_words(#[case] input: &str, #[case] expected: u32) {
+        let words = Vocabulary::new([
+            ("seven", 7),
+            ("nine", 9),
+            ("ten", 10),
+            ("eleven", 11),
+            ("twelve", 12),
+            ("seventeen", 17),
+            ("nineteen", 19),
+        ]);
+
+        assert_eq!(Some(expected), extract_code_with(input, &words));
+    }
+
+    #[rstest]
+    #[case("abc", None)]
+    #[case("", None)]
+    #[case("é7ü", Some(77))]
+    fn digits_only(#[case] input: &str, #[case] expected: Option<u32>) {
+        assert_eq!(
+            expected,
+            extract_code_with(input, &Vocabulary::new([("", 1)]))
+        );
+    }
 }
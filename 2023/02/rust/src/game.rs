@@ -1,5 +1,7 @@
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, PartialEq)]
 pub struct Game {
@@ -7,65 +9,137 @@ pub struct Game {
     pub sets: Vec<Set>,
 }
 
-#[derive(Debug, PartialEq)]
+/// Cube counts by color, where any color that was not drawn counts as zero.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Set {
-    pub red: u32,
-    pub green: u32,
-    pub blue: u32,
+    cubes: BTreeMap<String, u32>,
 }
 
 impl Set {
-    pub fn is_valid(&self, red_limit: u32, green_limit: u32, blue_limit: u32) -> bool {
-        self.red <= red_limit && self.green <= green_limit && self.blue <= blue_limit
+    pub fn count(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes.iter().map(|(c, &n)| (c.as_str(), n))
+    }
+
+    pub fn total(&self) -> u32 {
+        self.cubes.values().sum()
+    }
+
+    /// Whether the set could be drawn from `bag`, which rules out colors the bag does not hold.
+    pub fn is_valid(&self, bag: &Set) -> bool {
+        self.iter().all(|(color, n)| n <= bag.count(color))
     }
 
-    pub fn min_set(sets: Vec<Set>) -> Set {
+    pub fn min_set(sets: &[Set]) -> Set {
+        let mut min = Set::default();
+
+        for (color, n) in sets.iter().flat_map(Set::iter) {
+            let count = min.cubes.entry(color.to_owned()).or_default();
+            *count = (*count).max(n);
+        }
+
+        min
+    }
+
+    fn parse(input: &str) -> Result<Set, GameParseError> {
+        let mut cubes = BTreeMap::new();
+
+        for entry in input.split(',').map(str::trim) {
+            let cap = CUBES_REGEX
+                .captures(entry)
+                .ok_or_else(|| GameParseError::InvalidCubes(entry.to_owned()))?;
+            let count: u32 = cap[1]
+                .parse()
+                .map_err(|_| GameParseError::InvalidCount(cap[1].to_owned()))?;
+
+            if cubes.insert(cap[2].to_owned(), count).is_some() {
+                return Err(GameParseError::DuplicateColor(cap[2].to_owned()));
+            }
+        }
+
+        Ok(cubes.into_iter().collect())
+    }
+}
+
+/// Zero counts are left out, so that sets compare equal regardless of them.
+impl<S: Into<String>> FromIterator<(S, u32)> for Set {
+    fn from_iter<I: IntoIterator<Item = (S, u32)>>(iter: I) -> Self {
         Set {
-            red: sets.iter().map(|s| s.red).max().unwrap_or(0),
-            green: sets.iter().map(|s| s.green).max().unwrap_or(0),
-            blue: sets.iter().map(|s| s.blue).max().unwrap_or(0),
+            cubes: iter
+                .into_iter()
+                .filter(|&(_, n)| n > 0)
+                .map(|(c, n)| (c.into(), n))
+                .collect(),
+        }
+    }
+}
+
+impl<S: Into<String>, const N: usize> From<[(S, u32); N]> for Set {
+    fn from(cubes: [(S, u32); N]) -> Self {
+        cubes.into_iter().collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GameParseError {
+    FormError,
+    InvalidId(String),
+    InvalidCubes(String),
+    InvalidCount(String),
+    DuplicateColor(String),
+}
+
+impl Display for GameParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameParseError::FormError => {
+                write!(f, "Expected a line like \"Game 1: 3 blue, 4 red; 1 green\"")
+            }
+            GameParseError::InvalidId(s) => write!(f, "Invalid game id: {s}"),
+            GameParseError::InvalidCubes(s) => {
+                write!(f, "Expected cubes like \"3 blue\", got {s:?}")
+            }
+            GameParseError::InvalidCount(s) => write!(f, "Invalid cube count: {s}"),
+            GameParseError::DuplicateColor(c) => write!(f, "Color {c} drawn twice in one set"),
         }
     }
 }
 
+impl std::error::Error for GameParseError {}
+
 lazy_static! {
     static ref GAME_REGEX: Regex = Regex::new(r"^Game (\d+): (.*)$").unwrap();
-    static ref COLOR_REGEX: Regex = Regex::new(r"(\d+) (blue|red|green)").unwrap();
+    static ref CUBES_REGEX: Regex = Regex::new(r"^(\d+) (\w+)$").unwrap();
 }
 
 impl Game {
-    pub fn parse_from_string(input: &str) -> Game {
-        let mut game = Game {
-            id: 0,
-            sets: vec![],
-        };
-
-        let res = GAME_REGEX.captures(input);
-
-        let captures = res.unwrap();
-
-        game.id = captures[1].parse().unwrap();
-
-        for set_str in captures[2].split(';') {
-            let mut set = Set {
-                red: 0,
-                blue: 0,
-                green: 0,
-            };
-
-            for color_cap in COLOR_REGEX.captures_iter(set_str) {
-                let count: u32 = color_cap[1].parse().unwrap();
-                match &color_cap[2] {
-                    "red" => set.red = count,
-                    "green" => set.green = count,
-                    "blue" => set.blue = count,
-                    _ => {}
-                }
-            }
-            game.sets.push(set);
-        }
+    /// Smallest bag every set of the game could have been drawn from.
+    pub fn min_bag(&self) -> Set {
+        Set::min_set(&self.sets)
+    }
 
-        game
+    pub fn is_feasible(&self, bag: &Set) -> bool {
+        self.sets.iter().all(|s| s.is_valid(bag))
+    }
+
+    pub fn parse_from_string(input: &str) -> Result<Game, GameParseError> {
+        let captures = GAME_REGEX
+            .captures(input)
+            .ok_or(GameParseError::FormError)?;
+
+        let id = captures[1]
+            .parse()
+            .map_err(|_| GameParseError::InvalidId(captures[1].to_owned()))?;
+
+        let sets = captures[2]
+            .split(';')
+            .map(Set::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Game { id, sets })
     }
 }
 
@@ -76,16 +150,47 @@ mod tests {
 
     #[rstest]
     #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
-    Game{id: 1, sets: vec ! [Set{blue: 3, red: 4, green: 0}, Set{red: 1, green: 2, blue: 6}, Set{green: 2, red: 0, blue: 0}]})]
+    Game{id: 1, sets: vec ! [Set::from([("blue", 3), ("red", 4), ("green", 0)]), Set::from([("red", 1), ("green", 2), ("blue", 6)]), Set::from([("green", 2), ("red", 0), ("blue", 0)])]})]
     #[case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
-    Game{id: 2, sets: vec ! [Set{blue: 1, green: 2, red: 0}, Set{green: 3, blue: 4, red: 1}, Set{green: 1, blue: 1, red: 0}]})]
+    Game{id: 2, sets: vec ! [Set::from([("blue", 1), ("green", 2), ("red", 0)]), Set::from([("green", 3), ("blue", 4), ("red", 1)]), Set::from([("green", 1), ("blue", 1), ("red", 0)])]})]
     #[case("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-    Game{id: 3, sets: vec ! [Set{green: 8, blue: 6, red: 20}, Set{blue: 5, red: 4, green: 13}, Set{green: 5, red: 1, blue: 0}]})]
+    Game{id: 3, sets: vec ! [Set::from([("green", 8), ("blue", 6), ("red", 20)]), Set::from([("blue", 5), ("red", 4), ("green", 13)]), Set::from([("green", 5), ("red", 1), ("blue", 0)])]})]
     #[case("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-    Game{id: 4, sets: vec ! [Set{green: 1, red: 3, blue: 6}, Set{green: 3, red: 6, blue: 0}, Set{green: 3, blue: 15, red: 14}]})]
+    Game{id: 4, sets: vec ! [Set::from([("green", 1), ("red", 3), ("blue", 6)]), Set::from([("green", 3), ("red", 6), ("blue", 0)]), Set::from([("green", 3), ("blue", 15), ("red", 14)])]})]
     #[case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
-    Game{id: 5, sets: vec ! [Set{red: 6, blue: 1, green: 3}, Set{blue: 2, red: 1, green: 2}]})]
+    Game{id: 5, sets: vec ! [Set::from([("red", 6), ("blue", 1), ("green", 3)]), Set::from([("blue", 2), ("red", 1), ("green", 2)])]})]
     fn examples(#[case] input: String, #[case] expected: Game) {
         assert_eq!(Ok(expected), Game::parse_from_string(&input));
     }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 2 yellow; 1 yellow", vec![Set::from([("blue", 3), ("yellow", 2)]), Set::from([("yellow", 1)])])]
+    #[case("Game 1: 0 red, 1 dark_green", vec![Set::from([("dark_green", 1)])])]
+    fn any_color(#[case] input: &str, #[case] expected: Vec<Set>) {
+        assert_eq!(expected, Game::parse_from_string(input).unwrap().sets);
+    }
+
+    #[rstest]
+    #[case("Game 1 3 blue", GameParseError::FormError)]
+    #[case("Game 4294967296: 3 blue", GameParseError::InvalidId("4294967296".to_owned()))]
+    #[case("Game 1: 3 blue 4 red", GameParseError::InvalidCubes("3 blue 4 red".to_owned()))]
+    #[case("Game 1: 3 blue;", GameParseError::InvalidCubes("".to_owned()))]
+    #[case("Game 1: 4294967296 red", GameParseError::InvalidCount("4294967296".to_owned()))]
+    #[case("Game 1: 1 red, 2 red", GameParseError::DuplicateColor("red".to_owned()))]
+    fn invalid(#[case] input: &str, #[case] expected: GameParseError) {
+        assert_eq!(Err(expected), Game::parse_from_string(input));
+    }
+
+    #[test]
+    fn bags() {
+        let sets = [
+            Set::from([("red", 4), ("blue", 1)]),
+            Set::from([("red", 1), ("yellow", 2)]),
+        ];
+        let min = Set::min_set(&sets);
+
+        assert_eq!(Set::from([("red", 4), ("blue", 1), ("yellow", 2)]), min);
+        assert!(sets.iter().all(|s| s.is_valid(&min)));
+        assert!(!sets[1].is_valid(&Set::from([("red", 12), ("green", 13), ("blue", 14)])));
+    }
 }
@@ -0,0 +1,44 @@
+pub mod game;
+pub mod query;
+
+use aoc_common::{read_lines, Error, ParseError, Solution};
+use game::{Game, Set};
+use std::io::Read;
+
+/// Colors whose counts make up the power of a set.
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        read_lines(input)?
+            .iter()
+            .enumerate()
+            .map(|(i, l)| {
+                Game::parse_from_string(l).map_err(|e| ParseError::invalid(e).at_line(i, l).into())
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        let bag = Set::from([("red", 12), ("green", 13), ("blue", 14)]);
+
+        Ok(query::feasible(input, &bag).map(|g| g.id).sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input
+            .iter()
+            .map(Game::min_bag)
+            .map(|s| COLORS.iter().map(|c| s.count(c)).product::<u32>())
+            .sum())
+    }
+}
@@ -0,0 +1,145 @@
+//! Questions about a whole record of games rather than single sets.
+
+use crate::game::{Game, Set};
+use std::collections::BTreeSet;
+
+pub fn feasible<'a>(games: &'a [Game], bag: &'a Set) -> impl Iterator<Item = &'a Game> + 'a {
+    games.iter().filter(|g| g.is_feasible(bag))
+}
+
+/// Smallest bag that every chosen game could have been played with.
+pub fn min_bag<'a>(games: impl IntoIterator<Item = &'a Game>) -> Set {
+    let bags: Vec<Set> = games.into_iter().map(Game::min_bag).collect();
+    Set::min_set(&bags)
+}
+
+/// Largest subset of `games` that could all have been played with one bag holding no more than
+/// `bag` of every color and no more than `budget` cubes in total, along with the smallest such bag.
+///
+/// Tries every count of each color that some game needs exactly, so it takes up to `n^colors`
+/// steps for `n` games, which is fine for the handful of colors a record has.
+pub fn max_consistent<'a>(games: &'a [Game], bag: &Set, budget: u32) -> (Vec<&'a Game>, Set) {
+    let needs: Vec<(&Game, Set)> = games
+        .iter()
+        .filter(|g| g.is_feasible(bag))
+        .map(|g| (g, g.min_bag()))
+        .filter(|(_, need)| need.total() <= budget)
+        .collect();
+
+    let colors: BTreeSet<&str> = needs
+        .iter()
+        .flat_map(|(_, need)| need.iter().map(|(c, _)| c))
+        .collect();
+    let colors: Vec<&str> = colors.into_iter().collect();
+
+    let mut best = vec![];
+    search(
+        &needs,
+        &colors,
+        (0..needs.len()).collect(),
+        budget,
+        &mut best,
+    );
+
+    let chosen: Vec<&Game> = best.into_iter().map(|i| needs[i].0).collect();
+    let bag = min_bag(chosen.iter().copied());
+
+    (chosen, bag)
+}
+
+/// Picks a count for the first of `colors`, keeps the games needing no more than that and spends
+/// the rest of the budget on the remaining colors.
+fn search(
+    needs: &[(&Game, Set)],
+    colors: &[&str],
+    candidates: Vec<usize>,
+    remaining: u32,
+    best: &mut Vec<usize>,
+) {
+    // every later choice only drops games
+    if candidates.len() <= best.len() && !best.is_empty() {
+        return;
+    }
+
+    let Some((color, rest)) = colors.split_first() else {
+        *best = candidates;
+        return;
+    };
+
+    let counts: BTreeSet<u32> = candidates
+        .iter()
+        .map(|&i| needs[i].1.count(color))
+        .filter(|&n| n <= remaining)
+        .collect();
+
+    // largest first, as it keeps the most games and prunes the smaller counts
+    for &limit in counts.iter().rev() {
+        let kept = candidates
+            .iter()
+            .copied()
+            .filter(|&i| needs[i].1.count(color) <= limit)
+            .collect();
+
+        search(needs, rest, kept, remaining - limit, best);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn games() -> Vec<Game> {
+        [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]
+        .iter()
+        .map(|l| Game::parse_from_string(l).unwrap())
+        .collect()
+    }
+
+    fn ids<'a>(games: impl IntoIterator<Item = &'a Game>) -> Vec<u32> {
+        games.into_iter().map(|g| g.id).collect()
+    }
+
+    #[test]
+    fn feasible_games() {
+        let games = games();
+        let bag = Set::from([("red", 12), ("green", 13), ("blue", 14)]);
+
+        assert_eq!(vec![1, 2, 5], ids(feasible(&games, &bag)));
+    }
+
+    #[test]
+    fn min_bag_of_subset() {
+        let games = games();
+
+        assert_eq!(
+            Set::from([("red", 6), ("green", 3), ("blue", 6)]),
+            min_bag(games.iter().filter(|g| [1, 5].contains(&g.id)))
+        );
+        assert_eq!(Set::default(), min_bag([]));
+    }
+
+    #[test]
+    fn consistent_within_budget() {
+        let games = games();
+        let bag = Set::from([("red", 20), ("green", 13), ("blue", 15)]);
+
+        let (chosen, min) = max_consistent(&games, &bag, 100);
+        assert_eq!(vec![1, 2, 3, 4, 5], ids(chosen));
+        assert_eq!(Set::from([("red", 20), ("green", 13), ("blue", 15)]), min);
+
+        // games 1, 2 and 5 together need 6 red, 3 green and 6 blue
+        let (chosen, min) = max_consistent(&games, &bag, 15);
+        assert_eq!(vec![1, 2, 5], ids(chosen));
+        assert_eq!(Set::from([("red", 6), ("green", 3), ("blue", 6)]), min);
+
+        let (chosen, min) = max_consistent(&games, &bag, 5);
+        assert!(chosen.is_empty());
+        assert_eq!(Set::default(), min);
+    }
+}
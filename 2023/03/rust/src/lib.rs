@@ -0,0 +1,46 @@
+pub mod render;
+pub mod rules;
+pub mod schematic;
+
+use aoc_common::{Error, Solution};
+use schematic::Schematic;
+use std::io::Read;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        Schematic::parse_from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(input.find_part_numbers().map(|n| n.value).sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input.find_gear_ratios().sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_location() {
+        let input = "467..114..\n...*......\n.18446744073709551616*";
+
+        assert_eq!(
+            "Could not parse input: Number of 20 digits does not fit in 64 bits at line 3, column 2\n\
+             3 | .18446744073709551616*\n  |  ^",
+            Day03::parse(input.as_bytes()).err().unwrap().to_string()
+        );
+    }
+}
@@ -1,41 +1,42 @@
-use crate::schematic::Schematic;
+use aoc_2023_03::render::{render, Style};
+use aoc_2023_03::schematic::Schematic;
+use aoc_2023_03::Day03;
+use aoc_common::cli::Args;
+use aoc_common::{open, Error};
 use std::env;
-use std::fs::File;
-use std::io;
-use std::path::Path;
-
-mod schematic;
-
-fn main() -> io::Result<()> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 2 {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "No input file path provided",
-        ));
+use std::process::ExitCode;
+
+/// Besides the shared options, `--render <ansi|plain>` reprints the schematic with its part
+/// numbers and gears marked instead of solving it.
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let Some(i) = args.iter().position(|a| a == "--render") else {
+        return aoc_common::cli::run::<Day03>();
+    };
+    args.remove(i);
+    let style = if i < args.len() {
+        args.remove(i)
+    } else {
+        String::new()
+    };
+
+    match print(&args, &style) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
     }
+}
 
-    let path = Path::new(&args[1]);
-    let file = File::open(path)?;
-
-    let s = Schematic::parse_from_stream(file);
-
-    if s.is_err() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidInput,
-            "Could not parse input file into schematics",
-        ));
-    }
-
-    let schematic = s.unwrap();
-    let sum_parts: u64 = schematic.find_part_numbers().map(|n| n.value).sum();
-
-    println!("The sum of all part numbers is: {}", sum_parts);
+fn print(args: &[String], style: &str) -> Result<(), Error> {
+    let style = Style::try_from(style).map_err(Error::Usage)?;
 
-    let sum_gears: u64 = schematic.find_gear_ratios().sum();
+    let path = Args::parse(args)?.resolve_path(2023, 3)?;
 
-    println!("The sum of gear ratios is: {}", sum_gears);
+    let schematic = Schematic::parse_from_stream(open(&path)?)?;
+    print!("{}", render(&schematic, style));
 
     Ok(())
 }
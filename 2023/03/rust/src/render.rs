@@ -0,0 +1,152 @@
+//! Reprints a schematic with part numbers, other numbers and gears marked, to check which
+//! adjacency decisions were made.
+
+use crate::schematic::{Number, Schematic};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Style {
+    /// Part numbers in green, other numbers in red and gears in bold yellow.
+    Ansi,
+    /// Part numbers in `[]`, other numbers in `()` and gears in `{}`, which shifts the columns.
+    Plain,
+}
+
+impl TryFrom<&str> for Style {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "ansi" => Ok(Style::Ansi),
+            "plain" => Ok(Style::Plain),
+            _ => Err(format!("Unknown style {s:?}, expected ansi or plain")),
+        }
+    }
+}
+
+enum Mark {
+    Part,
+    Other,
+    Gear,
+}
+
+const RESET: &str = "\x1b[0m";
+
+impl Style {
+    fn mark(self, mark: Mark, text: &str) -> String {
+        let (open, close) = match (self, mark) {
+            (Style::Ansi, Mark::Part) => ("\x1b[32m", RESET),
+            (Style::Ansi, Mark::Other) => ("\x1b[31m", RESET),
+            (Style::Ansi, Mark::Gear) => ("\x1b[1;33m", RESET),
+            (Style::Plain, Mark::Part) => ("[", "]"),
+            (Style::Plain, Mark::Other) => ("(", ")"),
+            (Style::Plain, Mark::Gear) => ("{", "}"),
+        };
+
+        format!("{open}{text}{close}")
+    }
+}
+
+/// Every line of the schematic with its numbers and gears marked, followed by the ratios of the
+/// gears on it and their 0-based columns.
+pub fn render(schematic: &Schematic, style: Style) -> String {
+    let parts: HashSet<(u32, u32)> = schematic
+        .find_part_numbers()
+        .map(|n| (n.row, n.col_min))
+        .collect();
+    let gears: HashMap<(u32, u32), u64> = schematic
+        .find_gears()
+        .map(|(s, ratio)| ((s.row, s.col), ratio))
+        .collect();
+    let numbers: HashMap<(u32, u32), &Number> = schematic
+        .numbers
+        .iter()
+        .map(|n| ((n.row, n.col_min), n))
+        .collect();
+
+    let mut out = String::new();
+
+    for (row, line) in schematic.lines().iter().enumerate() {
+        let row = row as u32;
+        let chars: Vec<char> = line.chars().collect();
+        let mut ratios = vec![];
+        let mut col = 0;
+
+        while col < chars.len() {
+            let at = (row, col as u32);
+
+            if let Some(n) = numbers.get(&at) {
+                let text: String = chars[col..=n.col_max as usize].iter().collect();
+                let mark = if parts.contains(&at) {
+                    Mark::Part
+                } else {
+                    Mark::Other
+                };
+
+                out.push_str(&style.mark(mark, &text));
+                col = n.col_max as usize + 1;
+            } else if let Some(ratio) = gears.get(&at) {
+                out.push_str(&style.mark(Mark::Gear, &chars[col].to_string()));
+                ratios.push(format!("{} at {col}: {ratio}", chars[col]));
+                col += 1;
+            } else {
+                out.push(chars[col]);
+                col += 1;
+            }
+        }
+
+        if !ratios.is_empty() {
+            out.push_str("  ");
+            out.push_str(&ratios.join(", "));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stringreader::StringReader;
+
+    const EXAMPLE: &str = "467..114..\n\
+                           ...*......\n\
+                           ..35..633.\n\
+                           ......#...\n\
+                           617*......\n\
+                           .....+.58.\n\
+                           ..592.....\n\
+                           ......755.\n\
+                           ...$.*....\n\
+                           .664.598..";
+
+    #[test]
+    fn plain() {
+        let schematic = Schematic::parse_from_stream(StringReader::new(EXAMPLE)).unwrap();
+
+        assert_eq!(
+            "[467]..(114)..\n\
+             ...{*}......  * at 3: 16345\n\
+             ..[35]..[633].\n\
+             ......#...\n\
+             [617]*......\n\
+             .....+.(58).\n\
+             ..[592].....\n\
+             ......[755].\n\
+             ...$.{*}....  * at 5: 451490\n\
+             .[664].[598]..\n",
+            render(&schematic, Style::Plain)
+        );
+    }
+
+    #[test]
+    fn ansi() {
+        let schematic = Schematic::parse_from_stream(StringReader::new("1*2\n..3")).unwrap();
+
+        assert_eq!(
+            "\x1b[32m1\x1b[0m*\x1b[32m2\x1b[0m\n..\x1b[32m3\x1b[0m\n",
+            render(&schematic, Style::Ansi)
+        );
+    }
+}
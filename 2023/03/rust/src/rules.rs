@@ -0,0 +1,179 @@
+use crate::schematic::{Number, Symbol};
+
+/// Which neighbours of a cell count as adjacent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Only directly above, below, left or right.
+    Four,
+    /// Diagonals as well.
+    Eight,
+}
+
+impl Adjacency {
+    pub fn touches(self, number: &Number, symbol: &Symbol) -> bool {
+        let (row, col) = (symbol.row as i64, symbol.col as i64);
+        let (min, max) = (number.col_min as i64, number.col_max as i64);
+
+        match (self, row.abs_diff(number.row as i64)) {
+            (Adjacency::Eight, 0..=1) => min - 1 <= col && col <= max + 1,
+            (Adjacency::Four, 0) => col == min - 1 || col == max + 1,
+            (Adjacency::Four, 1) => min <= col && col <= max,
+            _ => false,
+        }
+    }
+}
+
+/// How the numbers around a gear are combined into its ratio.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+}
+
+impl Aggregation {
+    pub fn apply(self, values: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Aggregation::Product => values.product(),
+            Aggregation::Sum => values.sum(),
+        }
+    }
+}
+
+/// Reading of a schematic, so that variants of the puzzle only differ in configuration. The
+/// default is the puzzle's own: `.` is blank, every other non-digit is a symbol, and a `*`
+/// touching exactly two numbers, diagonals included, is a gear with their product as its ratio.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    blanks: Vec<char>,
+    symbols: Option<Vec<char>>,
+    gear: char,
+    gear_neighbors: usize,
+    adjacency: Adjacency,
+    aggregation: Aggregation,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            blanks: vec!['.'],
+            symbols: None,
+            gear: '*',
+            gear_neighbors: 2,
+            adjacency: Adjacency::Eight,
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+impl Rules {
+    pub fn with_blanks(self, blanks: impl IntoIterator<Item = char>) -> Self {
+        Rules {
+            blanks: blanks.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Only these characters are symbols, any other non-digit is treated as blank.
+    pub fn with_symbols(self, symbols: impl IntoIterator<Item = char>) -> Self {
+        Rules {
+            symbols: Some(symbols.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// The gear has to be a symbol itself to be found.
+    pub fn with_gear(self, gear: char, neighbors: usize) -> Self {
+        Rules {
+            gear,
+            gear_neighbors: neighbors,
+            ..self
+        }
+    }
+
+    pub fn with_adjacency(self, adjacency: Adjacency) -> Self {
+        Rules { adjacency, ..self }
+    }
+
+    pub fn with_aggregation(self, aggregation: Aggregation) -> Self {
+        Rules {
+            aggregation,
+            ..self
+        }
+    }
+
+    pub fn is_symbol(&self, c: char) -> bool {
+        !c.is_ascii_digit()
+            && !self.blanks.contains(&c)
+            && self.symbols.as_ref().is_none_or(|s| s.contains(&c))
+    }
+
+    pub fn is_gear(&self, symbol: &Symbol) -> bool {
+        symbol.char == self.gear
+    }
+
+    pub fn gear_neighbors(&self) -> usize {
+        self.gear_neighbors
+    }
+
+    pub fn adjacency(&self) -> Adjacency {
+        self.adjacency
+    }
+
+    pub fn aggregation(&self) -> Aggregation {
+        self.aggregation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Adjacency::Eight, 1, 4, true)]
+    #[case(Adjacency::Eight, 2, 1, true)]
+    #[case(Adjacency::Eight, 3, 5, true)]
+    #[case(Adjacency::Eight, 2, 6, false)]
+    #[case(Adjacency::Eight, 0, 2, false)]
+    #[case(Adjacency::Four, 2, 1, true)]
+    #[case(Adjacency::Four, 2, 5, true)]
+    #[case(Adjacency::Four, 1, 2, true)]
+    #[case(Adjacency::Four, 3, 4, true)]
+    #[case(Adjacency::Four, 1, 1, false)]
+    #[case(Adjacency::Four, 3, 5, false)]
+    #[case(Adjacency::Four, 2, 0, false)]
+    fn touches(
+        #[case] adjacency: Adjacency,
+        #[case] row: u32,
+        #[case] col: u32,
+        #[case] expected: bool,
+    ) {
+        // occupies columns 2 to 4 of row 2
+        let number = Number {
+            value: 123,
+            row: 2,
+            col_min: 2,
+            col_max: 4,
+        };
+        let symbol = Symbol {
+            row,
+            col,
+            char: '*',
+        };
+
+        assert_eq!(expected, adjacency.touches(&number, &symbol));
+    }
+
+    #[test]
+    fn symbol_classes() {
+        let rules = Rules::default()
+            .with_blanks(['.', ' '])
+            .with_symbols(['*', '#']);
+
+        assert!(rules.is_symbol('*'));
+        assert!(!rules.is_symbol('$'));
+        assert!(!rules.is_symbol(' '));
+        assert!(!rules.is_symbol('7'));
+        assert!(Rules::default().is_symbol('$'));
+    }
+}
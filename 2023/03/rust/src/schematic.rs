@@ -1,11 +1,26 @@
-use std::io::{self, BufRead, Read};
+use crate::rules::Rules;
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind};
+use std::io::Read;
+use std::iter::Peekable;
+use std::ops::{Range, RangeInclusive};
 
 pub struct Schematic {
     pub numbers: Vec<Number>,
     pub symbols: Vec<Symbol>,
+    lines: Vec<String>,
+    rules: Rules,
+    index: Index,
 }
 
-#[derive(Debug, PartialEq)]
+/// Ranges of `numbers` and `symbols` on every row. Both are parsed row by row and left to right,
+/// so a row holds a slice of each that is ordered by column.
+#[derive(Default)]
+struct Index {
+    numbers: Vec<Range<usize>>,
+    symbols: Vec<Range<usize>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Symbol {
     pub row: u32,
     pub col: u32,
@@ -20,112 +35,109 @@ pub struct Number {
     pub value: u64,
 }
 
+impl Number {
+    pub fn span(&self) -> RangeInclusive<u32> {
+        self.col_min..=self.col_max
+    }
+}
+
+/// Walks the characters of a row once, yielding every run of digits as a number as soon as it
+/// ends.
+struct Tokenizer<'a, I: Iterator<Item = (u32, char)>> {
+    row: u32,
+    line: &'a str,
+    chars: Peekable<I>,
+}
+
+impl<I: Iterator<Item = (u32, char)>> Iterator for Tokenizer<'_, I> {
+    type Item = Result<Number, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (col_min, first) = self.chars.find(|(_, c)| c.is_ascii_digit())?;
+
+        let mut col_max = col_min;
+        let mut value = first.to_digit(10).map(u64::from);
+
+        while let Some((col, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit()) {
+            col_max = col;
+            value = value.and_then(|v| v.checked_mul(10)?.checked_add(c.to_digit(10)? as u64));
+        }
+
+        Some(
+            value
+                .map(|value| Number {
+                    row: self.row,
+                    col_min,
+                    col_max,
+                    value,
+                })
+                .ok_or_else(|| {
+                    let digits = col_max - col_min + 1;
+                    let overflow = format!("Number of {digits} digits does not fit in 64 bits");
+
+                    ParseError::new(ParseErrorKind::Invalid(overflow)).at(
+                        self.row as usize,
+                        col_min as usize,
+                        self.line,
+                    )
+                }),
+        )
+    }
+}
+
 impl Schematic {
-    pub fn parse_from_stream(input: impl Read) -> Result<Schematic, String> {
+    pub fn parse_from_stream(input: impl Read) -> Result<Schematic, Error> {
+        Self::parse_with(input, Rules::default())
+    }
+
+    pub fn parse_with(input: impl Read, rules: Rules) -> Result<Schematic, Error> {
         let mut s = Schematic {
             numbers: vec![],
             symbols: vec![],
+            lines: read_lines(input)?,
+            rules,
+            index: Index::default(),
         };
 
-        let reader = io::BufReader::new(input);
-
-        for (row, line) in reader.lines().enumerate() {
-            if line.is_err() {
-                return Err(line.err().unwrap().to_string());
-            }
-
-            let chars = Self::enumerate_chars(line.unwrap());
-
-            let numbers = Self::parse_numbers(row as u32, chars.clone());
+        for (row, line) in s.lines.iter().enumerate() {
+            let numbers = Self::parse_numbers(row as u32, line)?;
+            let start = s.numbers.len();
             s.numbers.extend(numbers);
+            s.index.numbers.push(start..s.numbers.len());
 
-            let symbols = Self::parse_symbols(row as u32, chars);
+            let symbols = Self::parse_symbols(row as u32, line, &s.rules);
+            let start = s.symbols.len();
             s.symbols.extend(symbols);
+            s.index.symbols.push(start..s.symbols.len());
         }
 
         Ok(s)
     }
 
-    fn enumerate_chars(line: String) -> Vec<(u32, char)> {
-        line.chars()
-            .enumerate()
-            .map(|(i, c)| (i as u32, c))
-            .collect::<Vec<(u32, char)>>()
+    pub fn lines(&self) -> &[String] {
+        &self.lines
     }
 
-    fn parse_numbers(row: u32, chars: Vec<(u32, char)>) -> Vec<Number> {
-        let numeric: Vec<(u32, char)> = chars
-            .into_iter()
-            .filter(|(_, c)| c.is_numeric())
-            .collect::<Vec<_>>();
-
-        if numeric.len() == 1 {
-            return vec![Number {
-                value: numeric[0].1.to_digit(10).unwrap() as u64,
-                col_min: numeric[0].0,
-                col_max: numeric[0].0,
-                row,
-            }];
-        }
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
 
-        let last_index = numeric.iter().map(|(i, _)| i).last().unwrap_or(&0u32);
-
-        numeric
-            .windows(2)
-            .fold(vec![], |acc: Vec<Vec<(u32, char)>>, w| {
-                let prev = acc.last().and_then(|v| v.last());
-                let curr: (u32, char) = w[0];
-                let next: (u32, char) = w[1];
-
-                let new_acc: Vec<Vec<(u32, char)>> = if prev.is_none() || prev.unwrap().0 != curr.0
-                {
-                    let new_group = if next.0 == curr.0 + 1 {
-                        vec![curr, next]
-                    } else {
-                        vec![curr]
-                    };
-
-                    acc.into_iter().chain(vec![new_group]).collect()
-                } else if next.0 == curr.0 + 1 {
-                    let len = acc.len();
-                    let mut curr_group: Vec<(u32, char)> = acc.last().unwrap().clone();
-                    curr_group.push(next);
-
-                    acc.into_iter()
-                        .take(len - 1)
-                        .chain(vec![curr_group])
-                        .collect()
-                } else {
-                    acc
-                };
-
-                if next.0 == *last_index && new_acc.iter().last().unwrap().last().unwrap() != &next
-                {
-                    let last_group = vec![next];
-                    new_acc.into_iter().chain(vec![last_group]).collect()
-                } else {
-                    new_acc
-                }
-            })
-            .into_iter()
-            .map(|chars| Number {
-                value: chars
-                    .iter()
-                    .map(|c| c.1.to_digit(10).unwrap())
-                    .enumerate()
-                    .map(|(i, d)| 10u64.pow((chars.len() - i) as u32 - 1) * d as u64)
-                    .sum(),
-                col_min: chars.iter().map(|c| c.0).min().unwrap(),
-                col_max: chars.iter().map(|c| c.0).max().unwrap(),
-                row,
-            })
-            .collect()
+    fn enumerate_chars(line: &str) -> impl Iterator<Item = (u32, char)> + '_ {
+        line.chars().enumerate().map(|(i, c)| (i as u32, c))
     }
 
-    fn parse_symbols(row: u32, chars: Vec<(u32, char)>) -> Vec<Symbol> {
-        chars
-            .into_iter()
-            .filter(|(_, c)| !c.is_numeric() && *c != '.')
+    fn parse_numbers(row: u32, line: &str) -> Result<Vec<Number>, ParseError> {
+        Tokenizer {
+            row,
+            line,
+            chars: Self::enumerate_chars(line).peekable(),
+        }
+        .collect()
+    }
+
+    fn parse_symbols(row: u32, line: &str, rules: &Rules) -> Vec<Symbol> {
+        Self::enumerate_chars(line)
+            .filter(|&(_, c)| rules.is_symbol(c))
             .map(|(i, c)| Symbol {
                 col: i,
                 row,
@@ -134,54 +146,83 @@ impl Schematic {
             .collect()
     }
 
+    /// Slice of `items` on `row`, which is empty outside the schematic.
+    fn row<'a, T>(items: &'a [T], ranges: &[Range<usize>], row: i64) -> &'a [T] {
+        usize::try_from(row)
+            .ok()
+            .and_then(|r| ranges.get(r))
+            .map_or(&[], |r| &items[r.clone()])
+    }
+
+    /// Numbers the symbol touches, looking only at the rows around it.
+    pub fn neighbors_of(&self, symbol: &Symbol) -> impl Iterator<Item = &Number> + '_ {
+        let symbol = *symbol;
+        let col = symbol.col as i64;
+        let rows = symbol.row as i64 - 1..=symbol.row as i64 + 1;
+
+        rows.flat_map(move |r| {
+            let numbers = Self::row(&self.numbers, &self.index.numbers, r);
+
+            // numbers of a row do not overlap, so ordered by start they are ordered by end too
+            let from = numbers.partition_point(|n| (n.col_max as i64) < col - 1);
+            numbers[from..]
+                .iter()
+                .take_while(move |n| n.col_min as i64 <= col + 1)
+        })
+        .filter(move |n| self.rules.adjacency().touches(n, &symbol))
+    }
+
+    /// Symbols the number touches, looking only at the rows around it.
+    pub fn symbols_adjacent_to(&self, number: &Number) -> impl Iterator<Item = &Symbol> + '_ {
+        let number = *number;
+        let (min, max) = (number.col_min as i64, number.col_max as i64);
+        let rows = number.row as i64 - 1..=number.row as i64 + 1;
+
+        rows.flat_map(move |r| {
+            let symbols = Self::row(&self.symbols, &self.index.symbols, r);
+
+            let from = symbols.partition_point(|s| (s.col as i64) < min - 1);
+            symbols[from..]
+                .iter()
+                .take_while(move |s| s.col as i64 <= max + 1)
+        })
+        .filter(move |s| self.rules.adjacency().touches(&number, s))
+    }
+
     pub fn find_part_numbers(&self) -> impl Iterator<Item = Number> + '_ {
         self.numbers
             .iter()
-            .filter(|n| {
-                let rows = (n.row as i32 - 1)..=(n.row as i32 + 1);
-                let cols = (n.col_min as i32 - 1)..=(n.col_max as i32 + 1);
-                let coords: Vec<_> = rows
-                    .flat_map(|r| cols.clone().map(move |c| (r, c)))
-                    .collect();
-
-                self.symbols.iter().any(|s| {
-                    coords
-                        .iter()
-                        .any(|(r, c)| s.row as i32 == *r && s.col as i32 == *c)
-                })
-            })
+            .filter(|n| self.symbols_adjacent_to(n).next().is_some())
             .copied()
     }
 
-    pub fn find_gear_ratios(&self) -> impl Iterator<Item = u64> + '_ {
+    /// Gear symbols with exactly the required number of neighbours, along with their ratio.
+    pub fn find_gears(&self) -> impl Iterator<Item = (&Symbol, u64)> + '_ {
         self.symbols
             .iter()
-            .filter(|s| s.char == '*')
-            .map(|s| {
-                let rows = (s.row as i32 - 1)..=(s.row as i32 + 1);
-                let cols = (s.col as i32 - 1)..=(s.col as i32 + 1);
-
-                let coords: Vec<_> = rows
-                    .flat_map(|r| cols.clone().map(move |c| (r, c)))
-                    .collect();
-
-                self.numbers
-                    .iter()
-                    .filter(|n| {
-                        coords.iter().any(|(r, c)| {
-                            n.row as i32 == *r && n.col_min as i32 <= *c && n.col_max as i32 >= *c
-                        })
-                    })
-                    .collect::<Vec<_>>()
+            .filter(|s| self.rules.is_gear(s))
+            .filter_map(|s| {
+                let neighbors: Vec<&Number> = self.neighbors_of(s).collect();
+
+                (neighbors.len() == self.rules.gear_neighbors()).then(|| {
+                    let ratio = self
+                        .rules
+                        .aggregation()
+                        .apply(neighbors.iter().map(|n| n.value));
+                    (s, ratio)
+                })
             })
-            .filter(|p| p.len() == 2)
-            .map(|p| p[0].value * p[1].value)
+    }
+
+    pub fn find_gear_ratios(&self) -> impl Iterator<Item = u64> + '_ {
+        self.find_gears().map(|(_, ratio)| ratio)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rules::{Adjacency, Aggregation};
     use rstest::rstest;
     use stringreader::StringReader;
 
@@ -242,8 +283,37 @@ mod tests {
     #[case(".1.3......", 11, vec ! [Number{value: 1, row: 11, col_min: 1, col_max: 1}, Number{value: 3, row: 11, col_min: 3, col_max: 3}])]
     #[case(".........9", 12, vec ! [Number{value: 9, row: 12, col_min: 9, col_max: 9}])]
     fn parse_numbers(#[case] input: String, #[case] row: u32, #[case] expected: Vec<Number>) {
-        let chars = Schematic::enumerate_chars(input);
-        assert_eq!(expected, Schematic::parse_numbers(row, chars));
+        assert_eq!(Ok(expected), Schematic::parse_numbers(row, &input));
+    }
+
+    #[rstest]
+    #[case("18446744073709551615", 18446744073709551615)]
+    #[case("..0018446744073709551615..", 18446744073709551615)]
+    fn parse_widest(#[case] input: String, #[case] expected: u64) {
+        let numbers = Schematic::parse_numbers(0, &input).unwrap();
+
+        assert_eq!(
+            vec![expected],
+            numbers.iter().map(|n| n.value).collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case("18446744073709551616", 1, 20)]
+    #[case(".1.340282366920938463463374607431768211456*", 4, 39)]
+    fn parse_overflow(#[case] input: String, #[case] column: usize, #[case] digits: u32) {
+        let e = Schematic::parse_numbers(7, &input).unwrap_err();
+
+        assert_eq!(
+            ParseErrorKind::Invalid(format!("Number of {digits} digits does not fit in 64 bits")),
+            e.kind
+        );
+        assert_eq!(
+            Some((8, column, input.as_str())),
+            e.location
+                .as_ref()
+                .map(|l| (l.line, l.column, l.snippet.as_str()))
+        );
     }
 
     #[rstest]
@@ -258,7 +328,69 @@ mod tests {
     #[case("...$.*....", 8, vec ! [Symbol{row: 8, col: 3, char:'$'}, Symbol{row: 8, col: 5, char:'*'}])]
     #[case(".664.598..", 9, vec ! [])]
     fn parse_symbols(#[case] input: String, #[case] row: u32, #[case] expected: Vec<Symbol>) {
-        let chars = Schematic::enumerate_chars(input);
-        assert_eq!(expected, Schematic::parse_symbols(row, chars));
+        assert_eq!(
+            expected,
+            Schematic::parse_symbols(row, &input, &Rules::default())
+        );
+    }
+
+    const EXAMPLE: &str = "467..114..\n\
+                           ...*......\n\
+                           ..35..633.\n\
+                           ......#...\n\
+                           617*......\n\
+                           .....+.58.\n\
+                           ..592.....\n\
+                           ......755.\n\
+                           ...$.*....\n\
+                           .664.598..";
+
+    #[test]
+    fn adjacency_queries() {
+        let schematic = Schematic::parse_from_stream(StringReader::new(EXAMPLE)).unwrap();
+
+        let star = Symbol {
+            row: 1,
+            col: 3,
+            char: '*',
+        };
+        assert_eq!(
+            vec![467, 35],
+            schematic
+                .neighbors_of(&star)
+                .map(|n| n.value)
+                .collect::<Vec<_>>()
+        );
+
+        let number = schematic.numbers.iter().find(|n| n.value == 617).unwrap();
+        assert_eq!(
+            vec![&Symbol {
+                row: 4,
+                col: 3,
+                char: '*'
+            }],
+            schematic.symbols_adjacent_to(number).collect::<Vec<_>>()
+        );
+
+        let lonely = schematic.numbers.iter().find(|n| n.value == 114).unwrap();
+        assert_eq!(0, schematic.symbols_adjacent_to(lonely).count());
+    }
+
+    #[rstest]
+    #[case(Rules::default(), 4361, 467835)]
+    #[case(Rules::default().with_symbols(['*']), 4361 - 633 - 592 - 755 - 664 - 598 + 755 + 598, 467835)]
+    #[case(Rules::default().with_adjacency(Adjacency::Four), 617 + 35 + 633 + 664 + 598, 0)]
+    #[case(Rules::default().with_aggregation(Aggregation::Sum), 4361, 467 + 35 + 755 + 598)]
+    #[case(Rules::default().with_gear('#', 1), 4361, 633)]
+    #[case(Rules::default().with_gear('*', 1), 4361, 617)]
+    #[case(Rules::default().with_blanks(['.', '#', '+', '$']), 4361 - 633 - 592 - 664, 467835)]
+    fn variants(#[case] rules: Rules, #[case] part_numbers: u64, #[case] gear_ratios: u64) {
+        let schematic = Schematic::parse_with(StringReader::new(EXAMPLE), rules).unwrap();
+
+        assert_eq!(
+            part_numbers,
+            schematic.find_part_numbers().map(|n| n.value).sum::<u64>()
+        );
+        assert_eq!(gear_ratios, schematic.find_gear_ratios().sum::<u64>());
     }
 }
@@ -0,0 +1,100 @@
+pub mod scratch;
+
+use aoc_common::{read_lines, Error, ParseError, Solution};
+use scratch::{Card, CardParseError, CardPile, Mode};
+use std::io::Read;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+
+    type Input = CardPile;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        parse_cards(input, Mode::Lenient)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        input
+            .score()
+            .ok_or(Error::Solve("Points do not fit in 64 bits".to_owned()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        let overflow = || Error::Solve("Card counts do not fit in 64 bits".to_owned());
+
+        input
+            .cascade()
+            .ok_or_else(overflow)?
+            .iter()
+            .try_fold(0u64, |sum, c| sum.checked_add(c.count))
+            .ok_or_else(overflow)
+    }
+}
+
+/// Strict parsing also rejects cards whose ids do not count up from 1.
+pub fn parse_cards(input: impl Read, mode: Mode) -> Result<CardPile, Error> {
+    let lines = read_lines(input)?;
+    let mut cards = Vec::with_capacity(lines.len());
+
+    for (i, l) in lines.iter().enumerate() {
+        let located = |e: CardParseError| ParseError::invalid(e).at_line(i, l);
+        let card = Card::parse(l, mode).map_err(located)?;
+
+        let expected = cards.len() as u32 + 1;
+        if mode == Mode::Strict && card.id != expected {
+            return Err(located(CardParseError::UnexpectedId {
+                expected,
+                found: card.id,
+            })
+            .into());
+        }
+
+        cards.push(card);
+    }
+
+    Ok(CardPile::new(cards))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "Card 1: 1 2 | 3\nCard 3: 1 | 1",
+        "Expected card 2, found card 3 at line 2"
+    )]
+    #[case("Card 2: 1 2 | 3", "Expected card 1, found card 2 at line 1")]
+    #[case(
+        "Card 3: 1 | 1\nCard 5: 1 | 1",
+        "Expected card 1, found card 3 at line 1"
+    )]
+    #[case(
+        "Card 1: 1 2 | 3\nCard 2: 5 5 | 5",
+        "Winning number 5 appears twice at line 2"
+    )]
+    #[case("Card 1: 1 2 | 3 4 3", "Played number 3 appears twice at line 1")]
+    fn strict(#[case] input: &str, #[case] expected: &str) {
+        let e = parse_cards(input.as_bytes(), Mode::Strict).unwrap_err();
+        assert!(e.to_string().contains(expected), "{e}");
+
+        assert!(parse_cards(input.as_bytes(), Mode::Lenient).is_ok());
+    }
+
+    #[test]
+    fn part2_overflow() {
+        let numbers = "1 2 3 4 5 6 7 8 9 10";
+        let input: String = (1..=120)
+            .map(|id| format!("Card {id}: {numbers} | {numbers}\n"))
+            .collect();
+        let pile = Day04::parse(input.as_bytes()).unwrap();
+
+        assert!(matches!(Day04::part2(&pile), Err(Error::Solve(_))));
+    }
+}
@@ -1,5 +1,7 @@
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 lazy_static! {
@@ -7,91 +9,308 @@ lazy_static! {
         Regex::new(r"^Card\s+(\d+):\s+((?:\d+\s+)*\d+)\s+\|\s+((?:\d+\s+)*\d+)$").unwrap();
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Card {
     pub id: u32,
-    pub n_instances: u64,
     winning: Vec<u32>,
     played: Vec<u32>,
 }
 
+/// Lenient parsing takes a card as written, strict parsing also rejects a number that appears
+/// twice on the same side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Lenient,
+    Strict,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CardParseError {
+    FormError,
+    InvalidNumber(String),
+    DuplicateWinning(u32),
+    DuplicatePlayed(u32),
+    UnexpectedId { expected: u32, found: u32 },
+}
+
+impl Display for CardParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardParseError::FormError => {
+                write!(f, "Expected a card like \"Card 1: 41 48 | 83 86 6\"")
+            }
+            CardParseError::InvalidNumber(s) => write!(f, "Invalid number: {s}"),
+            CardParseError::DuplicateWinning(n) => write!(f, "Winning number {n} appears twice"),
+            CardParseError::DuplicatePlayed(n) => write!(f, "Played number {n} appears twice"),
+            CardParseError::UnexpectedId { expected, found } => {
+                write!(f, "Expected card {expected}, found card {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CardParseError {}
+
 impl FromStr for Card {
-    type Err = regex::Error;
+    type Err = CardParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Card::parse(input, Mode::Lenient)
+    }
+}
+
+impl Card {
+    pub fn parse(input: &str, mode: Mode) -> Result<Card, CardParseError> {
         let caps = CARD_REGEX
             .captures(input)
-            .ok_or(regex::Error::Syntax("Invalid format".into()))?;
+            .ok_or(CardParseError::FormError)?;
+
+        let number = |s: &str| {
+            s.parse()
+                .map_err(|_| CardParseError::InvalidNumber(s.to_owned()))
+        };
+
+        let numbers = |s: &str, duplicate: fn(u32) -> CardParseError| {
+            let mut seen = HashSet::new();
+
+            s.split_whitespace()
+                .map(|n| {
+                    let n = number(n)?;
+                    if mode == Mode::Strict && !seen.insert(n) {
+                        return Err(duplicate(n));
+                    }
+                    Ok(n)
+                })
+                .collect::<Result<Vec<u32>, _>>()
+        };
 
         Ok(Card {
-            id: caps[1].parse().unwrap(),
-            winning: caps[2]
-                .split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect(),
-            played: caps[3]
-                .split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect(),
-            n_instances: 1,
+            id: number(&caps[1])?,
+            winning: numbers(&caps[2], CardParseError::DuplicateWinning)?,
+            played: numbers(&caps[3], CardParseError::DuplicatePlayed)?,
         })
     }
-}
 
-impl Card {
+    /// Distinct played numbers that are also winning, so a number written twice counts once.
     pub fn matching_count(&self) -> u32 {
-        self.played
-            .iter()
-            .filter(|n| self.winning.contains(n))
-            .count() as u32
+        // card numbers stay below 100, which fits a bitset in a single u128
+        if self.winning.iter().chain(&self.played).all(|&n| n < 128) {
+            let bits = |numbers: &[u32]| numbers.iter().fold(0u128, |bits, &n| bits | 1 << n);
+
+            return (bits(&self.winning) & bits(&self.played)).count_ones();
+        }
+
+        let winning: HashSet<u32> = self.winning.iter().copied().collect();
+        let played: HashSet<u32> = self.played.iter().copied().collect();
+
+        winning.intersection(&played).count() as u32
+    }
+
+    pub fn score(&self) -> Option<u64> {
+        Scoring::Doubling.score(self.matching_count())
     }
+}
 
-    pub fn score(&self) -> u64 {
-        let matching = self.matching_count();
+/// Points a card is worth for its number of matches.
+#[derive(Debug, Clone, Copy)]
+pub enum Scoring {
+    /// One point for the first match, doubled for every further one.
+    Doubling,
+    /// One point per match.
+    Linear,
+    Custom(fn(u32) -> u64),
+}
 
-        if matching == 0 {
-            0
-        } else {
-            2u64.pow(matching - 1)
+impl Scoring {
+    /// `None` once the points do not fit in 64 bits, which doubling reaches at 65 matches.
+    pub fn score(self, matching: u32) -> Option<u64> {
+        match self {
+            Scoring::Doubling if matching == 0 => Some(0),
+            Scoring::Doubling => 2u64.checked_pow(matching - 1),
+            Scoring::Linear => Some(matching as u64),
+            Scoring::Custom(f) => Some(f(matching)),
         }
     }
 }
 
+/// Copies of a card held once the cascade is over.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Instances {
+    pub id: u32,
+    /// The original card along with every copy won.
+    pub count: u64,
+    /// Ids of the cards that won copies of this one, with how many each of them won.
+    pub won_from: Vec<(u32, u64)>,
+}
+
+/// Cards in the order they were dealt, where every instance of a card with `n` matches wins a
+/// copy of each of the `n` cards after it.
+#[derive(Debug, Clone)]
+pub struct CardPile {
+    cards: Vec<Card>,
+    scoring: Scoring,
+}
+
+impl CardPile {
+    pub fn new(cards: Vec<Card>) -> Self {
+        CardPile {
+            cards,
+            scoring: Scoring::Doubling,
+        }
+    }
+
+    pub fn with_scoring(self, scoring: Scoring) -> Self {
+        CardPile { scoring, ..self }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Points of every original card, leaving the copies out, or `None` if they overflow.
+    pub fn score(&self) -> Option<u64> {
+        self.cards.iter().try_fold(0u64, |sum, c| {
+            sum.checked_add(self.scoring.score(c.matching_count())?)
+        })
+    }
+
+    /// Copies never reach past the last card, so the cascade settles in one pass. `None` once a
+    /// card's copies do not fit in 64 bits.
+    pub fn cascade(&self) -> Option<Vec<Instances>> {
+        let mut instances: Vec<Instances> = self
+            .cards
+            .iter()
+            .map(|c| Instances {
+                id: c.id,
+                count: 1,
+                won_from: vec![],
+            })
+            .collect();
+
+        for (i, card) in self.cards.iter().enumerate() {
+            let won = instances[i].count;
+            let last = (i + card.matching_count() as usize).min(self.cards.len() - 1);
+
+            for copy in &mut instances[i + 1..=last] {
+                copy.count = copy.count.checked_add(won)?;
+                copy.won_from.push((card.id, won));
+            }
+        }
+
+        Some(instances)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
 
     #[rstest]
-    #[case(Card{id: 1, n_instances: 1, winning: vec ! [41, 48, 83, 86, 17], played: vec ! [83, 86, 6, 31, 17, 9, 48, 53]}, 4)]
-    #[case(Card{id: 2, n_instances: 1, winning: vec ! [13, 32, 20, 16, 61], played: vec ! [61, 30, 68, 82, 17, 32, 24, 19]}, 2)]
-    #[case(Card{id: 3, n_instances: 1, winning: vec ! [1, 21, 53, 59, 44], played: vec ! [69, 82, 63, 72, 16, 21, 14, 1]}, 2)]
-    #[case(Card{id: 4, n_instances: 1, winning: vec ! [41, 92, 73, 84, 69], played: vec ! [59, 84, 76, 51, 58, 5, 54, 83]}, 1)]
-    #[case(Card{id: 5, n_instances: 1, winning: vec ! [87, 83, 26, 28, 32], played: vec ! [88, 30, 70, 12, 93, 22, 82, 36]}, 0)]
-    #[case(Card{id: 6, n_instances: 1, winning: vec ! [31, 18, 13, 56, 72], played: vec ! [74, 77, 10, 23, 35, 67, 36, 11]}, 0)]
+    #[case(Card{id: 1, winning: vec ! [41, 48, 83, 86, 17], played: vec ! [83, 86, 6, 31, 17, 9, 48, 53]}, 4)]
+    #[case(Card{id: 2, winning: vec ! [13, 32, 20, 16, 61], played: vec ! [61, 30, 68, 82, 17, 32, 24, 19]}, 2)]
+    #[case(Card{id: 3, winning: vec ! [1, 21, 53, 59, 44], played: vec ! [69, 82, 63, 72, 16, 21, 14, 1]}, 2)]
+    #[case(Card{id: 4, winning: vec ! [41, 92, 73, 84, 69], played: vec ! [59, 84, 76, 51, 58, 5, 54, 83]}, 1)]
+    #[case(Card{id: 5, winning: vec ! [87, 83, 26, 28, 32], played: vec ! [88, 30, 70, 12, 93, 22, 82, 36]}, 0)]
+    #[case(Card{id: 6, winning: vec ! [31, 18, 13, 56, 72], played: vec ! [74, 77, 10, 23, 35, 67, 36, 11]}, 0)]
     fn matching_count_examples(#[case] input: Card, #[case] expected: u32) {
         assert_eq!(expected, input.matching_count());
     }
 
     #[rstest]
-    #[case(Card{id: 1, n_instances: 1, winning: vec ! [41, 48, 83, 86, 17], played: vec ! [83, 86, 6, 31, 17, 9, 48, 53]}, 8)]
-    #[case(Card{id: 2, n_instances: 1, winning: vec ! [13, 32, 20, 16, 61], played: vec ! [61, 30, 68, 82, 17, 32, 24, 19]}, 2)]
-    #[case(Card{id: 3, n_instances: 1, winning: vec ! [1, 21, 53, 59, 44], played: vec ! [69, 82, 63, 72, 16, 21, 14, 1]}, 2)]
-    #[case(Card{id: 4, n_instances: 1, winning: vec ! [41, 92, 73, 84, 69], played: vec ! [59, 84, 76, 51, 58, 5, 54, 83]}, 1)]
-    #[case(Card{id: 5, n_instances: 1, winning: vec ! [87, 83, 26, 28, 32], played: vec ! [88, 30, 70, 12, 93, 22, 82, 36]}, 0)]
-    #[case(Card{id: 6, n_instances: 1, winning: vec ! [31, 18, 13, 56, 72], played: vec ! [74, 77, 10, 23, 35, 67, 36, 11]}, 0)]
+    #[case(Card{id: 1, winning: vec ! [41, 48, 83, 86, 17], played: vec ! [83, 86, 6, 31, 17, 9, 48, 53]}, 8)]
+    #[case(Card{id: 2, winning: vec ! [13, 32, 20, 16, 61], played: vec ! [61, 30, 68, 82, 17, 32, 24, 19]}, 2)]
+    #[case(Card{id: 3, winning: vec ! [1, 21, 53, 59, 44], played: vec ! [69, 82, 63, 72, 16, 21, 14, 1]}, 2)]
+    #[case(Card{id: 4, winning: vec ! [41, 92, 73, 84, 69], played: vec ! [59, 84, 76, 51, 58, 5, 54, 83]}, 1)]
+    #[case(Card{id: 5, winning: vec ! [87, 83, 26, 28, 32], played: vec ! [88, 30, 70, 12, 93, 22, 82, 36]}, 0)]
+    #[case(Card{id: 6, winning: vec ! [31, 18, 13, 56, 72], played: vec ! [74, 77, 10, 23, 35, 67, 36, 11]}, 0)]
     fn score_examples(#[case] input: Card, #[case] expected: u64) {
-        assert_eq!(expected, input.score());
+        assert_eq!(Some(expected), input.score());
     }
 
     #[rstest]
-    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", Card{id: 1, n_instances: 1, winning: vec ! [41, 48, 83, 86, 17], played: vec ! [83, 86, 6, 31, 17, 9, 48, 53]})]
-    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", Card{id: 2, n_instances: 1, winning: vec ! [13, 32, 20, 16, 61], played: vec ! [61, 30, 68, 82, 17, 32, 24, 19]})]
-    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", Card{id: 3, n_instances: 1, winning: vec ! [1, 21, 53, 59, 44], played: vec ! [69, 82, 63, 72, 16, 21, 14, 1]})]
-    #[case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", Card{id: 4, n_instances: 1, winning: vec ! [41, 92, 73, 84, 69], played: vec ! [59, 84, 76, 51, 58, 5, 54, 83]})]
-    #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", Card{id: 5, n_instances: 1, winning: vec ! [87, 83, 26, 28, 32], played: vec ! [88, 30, 70, 12, 93, 22, 82, 36]})]
-    #[case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", Card{id: 6, n_instances: 1, winning: vec ! [31, 18, 13, 56, 72], played: vec ! [74, 77, 10, 23, 35, 67, 36, 11]})]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", Card{id: 1, winning: vec ! [41, 48, 83, 86, 17], played: vec ! [83, 86, 6, 31, 17, 9, 48, 53]})]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", Card{id: 2, winning: vec ! [13, 32, 20, 16, 61], played: vec ! [61, 30, 68, 82, 17, 32, 24, 19]})]
+    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", Card{id: 3, winning: vec ! [1, 21, 53, 59, 44], played: vec ! [69, 82, 63, 72, 16, 21, 14, 1]})]
+    #[case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", Card{id: 4, winning: vec ! [41, 92, 73, 84, 69], played: vec ! [59, 84, 76, 51, 58, 5, 54, 83]})]
+    #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", Card{id: 5, winning: vec ! [87, 83, 26, 28, 32], played: vec ! [88, 30, 70, 12, 93, 22, 82, 36]})]
+    #[case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", Card{id: 6, winning: vec ! [31, 18, 13, 56, 72], played: vec ! [74, 77, 10, 23, 35, 67, 36, 11]})]
     fn parse_examples(#[case] input: String, #[case] expected: Card) {
         assert_eq!(Ok(expected), input.parse::<Card>());
     }
+
+    fn example() -> CardPile {
+        CardPile::new(
+            [
+                "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+                "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+                "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+                "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+                "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+                "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+            ]
+            .iter()
+            .map(|l| l.parse().unwrap())
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn cascade() {
+        let instances = example().cascade().unwrap();
+
+        assert_eq!(
+            vec![1, 2, 4, 8, 14, 1],
+            instances.iter().map(|i| i.count).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![(1, 1), (2, 2), (3, 4)], instances[3].won_from);
+        assert!(instances[5].won_from.is_empty());
+    }
+
+    #[test]
+    fn cascade_overflow() {
+        let cards = (1..=120)
+            .map(|id| Card {
+                id,
+                winning: (1..=10).collect(),
+                played: (1..=10).collect(),
+            })
+            .collect();
+
+        assert_eq!(None, CardPile::new(cards).cascade());
+    }
+
+    #[rstest]
+    #[case(Scoring::Doubling, 13)]
+    #[case(Scoring::Linear, 9)]
+    #[case(Scoring::Custom(|m| 10 * m as u64), 90)]
+    fn scoring_rules(#[case] scoring: Scoring, #[case] expected: u64) {
+        assert_eq!(Some(expected), example().with_scoring(scoring).score());
+    }
+
+    #[rstest]
+    #[case(64, Some(1 << 63))]
+    #[case(65, None)]
+    #[case(u32::MAX, None)]
+    fn doubling_overflow(#[case] matching: u32, #[case] expected: Option<u64>) {
+        assert_eq!(expected, Scoring::Doubling.score(matching));
+    }
+
+    #[rstest]
+    #[case("Card 1: 5 5 7 | 5 5 8", 1)]
+    #[case("Card 1: 5 7 | 5 5 7 7", 2)]
+    #[case("Card 1: 500 127 7 500 | 500 128 127 500", 2)]
+    fn duplicates_count_once(#[case] input: &str, #[case] expected: u32) {
+        assert_eq!(expected, input.parse::<Card>().unwrap().matching_count());
+    }
+
+    #[rstest]
+    #[case("Card 1 1 | 2", CardParseError::FormError)]
+    #[case("Card 1: 1 | 99999999999", CardParseError::InvalidNumber("99999999999".to_owned()))]
+    #[case("Card 1: 1 2 1 | 2", CardParseError::DuplicateWinning(1))]
+    #[case("Card 1: 1 | 2 2", CardParseError::DuplicatePlayed(2))]
+    fn strict_errors(#[case] input: &str, #[case] expected: CardParseError) {
+        assert_eq!(Err(expected), Card::parse(input, Mode::Strict));
+    }
 }
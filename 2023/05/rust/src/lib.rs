@@ -0,0 +1,66 @@
+pub mod part1;
+pub mod part2;
+
+use aoc_common::{Error, Solution};
+use std::io::Read;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 5;
+
+    type Input = (part1::Almanac, part2::Almanac);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(mut input: impl Read) -> Result<Self::Input, Error> {
+        let mut buf = vec![];
+        input.read_to_end(&mut buf)?;
+
+        let almanac1 = part1::Almanac::parse_from_stream(&mut &buf[..])?;
+        let almanac2 = part2::Almanac::parse_from_stream(&mut &buf[..])?;
+
+        Ok((almanac1, almanac2))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        input
+            .0
+            .closest_seed_loc()
+            .ok_or(Error::Solve("No seed location found".to_owned()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        input
+            .1
+            .closest_seed_loc_intervals()
+            .ok_or(Error::Solve("No seed location found".to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("seed-to-soil map:", "Missing seed list at line 1")]
+    #[case("seeds: 1 2\n1 2 3", "Missing connection header at line 2")]
+    #[case(
+        "seeds: 1 2\n\nseed-to-soil map:\n1 2 x",
+        "Expected connection exception at line 4"
+    )]
+    #[case(
+        "seeds: 1 2\n\nseed-to-soil map:\n18446744073709551615 0 1",
+        "Connection exception range does not fit in 64 bits at line 4"
+    )]
+    #[case(
+        "seeds: 1 2\n\nseed-to-soil map:\n0 18446744073709551615 1",
+        "Connection exception range does not fit in 64 bits at line 4"
+    )]
+    fn located(#[case] input: &str, #[case] expected: &str) {
+        let e = Day05::parse(input.as_bytes()).unwrap_err();
+        assert!(e.to_string().contains(expected), "{e}");
+    }
+}
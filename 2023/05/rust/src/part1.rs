@@ -1,8 +1,8 @@
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind};
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::error::Error;
-use std::fmt::{Display, Formatter};
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
+use tracing::trace;
 
 #[derive(Debug)]
 pub struct Almanac {
@@ -30,34 +30,15 @@ lazy_static! {
     static ref CONNECTION_EXCEPTION_REGEX: Regex = Regex::new(r"^(\d+)\s+(\d+)\s+(\d+)$").unwrap();
 }
 
-#[derive(Debug)]
-pub enum AlmanacParseError {
-    IoError(std::io::Error),
-    FormError(String),
-}
-
-impl Display for AlmanacParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AlmanacParseError::IoError(e) => write!(f, "Could not read: {}", e),
-            AlmanacParseError::FormError(s) => write!(f, "Unexpected input: {}", s),
-        }
-    }
-}
-
-impl Error for AlmanacParseError {}
-
 impl Almanac {
-    pub fn parse_from_stream(input: &mut impl Read) -> Result<Almanac, AlmanacParseError> {
+    pub fn parse_from_stream(input: &mut impl Read) -> Result<Almanac, Error> {
         let mut almanac = Almanac {
             seeds: vec![],
             connections: vec![],
         };
 
-        let reader = BufReader::new(input);
-
-        for line_result in reader.lines() {
-            let line = line_result.map_err(AlmanacParseError::IoError)?;
+        for (i, line) in read_lines(input)?.into_iter().enumerate() {
+            let located = |k| ParseError::new(k).at_line(i, &line);
 
             if line.is_empty() {
                 continue;
@@ -68,9 +49,7 @@ impl Almanac {
                     almanac.seeds = seeds;
                     continue;
                 } else {
-                    return Err(AlmanacParseError::FormError(
-                        "Expected seed list".to_string(),
-                    ));
+                    return Err(located(ParseErrorKind::Missing("seed list".to_owned())).into());
                 }
             }
 
@@ -81,28 +60,37 @@ impl Almanac {
 
             let last_conn = almanac.connections.last_mut();
             if last_conn.is_none() {
-                return Err(AlmanacParseError::FormError(
-                    "Expected connection header".to_string(),
-                ));
+                return Err(
+                    located(ParseErrorKind::Missing("connection header".to_owned())).into(),
+                );
             }
 
             if let Some(ex) = Self::parse_exception(&line) {
+                // ends past u64::MAX would overflow when checking or shifting values
+                if ex.src.checked_add(ex.count).is_none() || ex.dst.checked_add(ex.count).is_none()
+                {
+                    return Err(located(ParseErrorKind::Invalid(
+                        "Connection exception range does not fit in 64 bits".to_owned(),
+                    ))
+                    .into());
+                }
                 last_conn.unwrap().exceptions.push(ex);
             } else {
-                return Err(AlmanacParseError::FormError(
-                    "Expected connection exception".to_string(),
-                ));
+                return Err(located(ParseErrorKind::Invalid(
+                    "Expected connection exception".to_owned(),
+                ))
+                .into());
             }
         }
 
         if almanac.seeds.is_empty() {
-            return Err(AlmanacParseError::FormError("Seeds empty".to_string()));
+            return Err(ParseError::new(ParseErrorKind::EmptyInput).into());
         }
 
         if almanac.connections.is_empty() {
-            return Err(AlmanacParseError::FormError(
-                "Connections empty".to_string(),
-            ));
+            return Err(
+                ParseError::new(ParseErrorKind::Missing("connection maps".to_owned())).into(),
+            );
         }
 
         Ok(almanac)
@@ -121,10 +109,10 @@ impl Almanac {
                 curr
             };
 
-            println!("{} {} -> {} {}", c.src, curr, c.dst, next);
+            trace!(from = %c.src, to = %c.dst, curr, next, "propagated");
             curr = next
         }
-        println!("Found location {}\n", curr);
+        trace!(location = curr, "found location");
         curr
     }
 
@@ -134,12 +122,10 @@ impl Almanac {
 
     fn parse_seeds(line: &str) -> Option<Vec<u64>> {
         let cap = SEEDS_REGEX.captures(line)?;
-        Some(
-            cap[1]
-                .split(' ')
-                .map(|part| part.trim().parse::<u64>().unwrap())
-                .collect(),
-        )
+        cap[1]
+            .split_whitespace()
+            .map(|part| part.parse::<u64>().ok())
+            .collect()
     }
 
     fn parse_header(line: &str) -> Option<Connection> {
@@ -154,9 +140,9 @@ impl Almanac {
     fn parse_exception(line: &str) -> Option<ConnectionException> {
         let cap = CONNECTION_EXCEPTION_REGEX.captures(line)?;
         Some(ConnectionException {
-            dst: cap[1].parse().unwrap(),
-            src: cap[2].parse().unwrap(),
-            count: cap[3].parse().unwrap(),
+            dst: cap[1].parse().ok()?,
+            src: cap[2].parse().ok()?,
+            count: cap[3].parse().ok()?,
         })
     }
 }
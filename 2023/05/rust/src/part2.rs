@@ -1,8 +1,9 @@
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind};
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::error::Error;
-use std::fmt::{Display, Formatter};
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
+use std::ops::Range;
+use tracing::{debug, trace};
 
 #[derive(Debug)]
 pub struct Almanac {
@@ -30,40 +31,81 @@ struct ConnectionException {
     count: u64,
 }
 
-lazy_static! {
-    static ref SEEDS_REGEX: Regex = Regex::new(r"^seeds: ([\d\s]*\d+)$").unwrap();
-    static ref CONNECTION_HEADER_REGEX: Regex = Regex::new(r"^(\w+)-to-(\w+) map:$").unwrap();
-    static ref CONNECTION_EXCEPTION_REGEX: Regex = Regex::new(r"^(\d+)\s+(\d+)\s+(\d+)$").unwrap();
-}
+impl Connection {
+    /// Maps half-open ranges through the connection, splitting them at exception boundaries so
+    /// that every piece is shifted as a whole. Like `find_location`, the first exception in the
+    /// list wins where they overlap, and values outside every exception map to themselves.
+    fn map_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut mapped = vec![];
+        // kept sorted, so the pieces past an exception's end can all be skipped at once
+        let mut unmapped = merge(ranges.to_vec());
+
+        for e in &self.exceptions {
+            let end = e.src.saturating_add(e.count);
+            let mut rest = Vec::with_capacity(unmapped.len() + 1);
+            let mut pieces = unmapped.into_iter();
+
+            for range in pieces.by_ref() {
+                if range.start >= end {
+                    rest.push(range);
+                    break;
+                }
 
-#[derive(Debug)]
-pub enum AlmanacParseError {
-    IoError(std::io::Error),
-    FormError(String),
+                let (from, to) = (range.start.max(e.src), range.end.min(end));
+                if from >= to {
+                    rest.push(range);
+                    continue;
+                }
+
+                if range.start < from {
+                    rest.push(range.start..from);
+                }
+                mapped.push(e.dst.saturating_add(from - e.src)..e.dst.saturating_add(to - e.src));
+                if to < range.end {
+                    rest.push(to..range.end);
+                }
+            }
+
+            rest.extend(pieces);
+            unmapped = rest;
+        }
+
+        mapped.extend(unmapped);
+        merge(mapped)
+    }
 }
 
-impl Display for AlmanacParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AlmanacParseError::IoError(e) => write!(f, "Could not read: {}", e),
-            AlmanacParseError::FormError(s) => write!(f, "Unexpected input: {}", s),
+/// Sorts the ranges and joins the ones that overlap or touch, dropping empty ones.
+fn merge(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.retain(|r| r.start < r.end);
+    ranges.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => merged.push(r),
         }
     }
+
+    merged
 }
 
-impl Error for AlmanacParseError {}
+lazy_static! {
+    static ref SEEDS_REGEX: Regex = Regex::new(r"^seeds: ([\d\s]*\d+)$").unwrap();
+    static ref CONNECTION_HEADER_REGEX: Regex = Regex::new(r"^(\w+)-to-(\w+) map:$").unwrap();
+    static ref CONNECTION_EXCEPTION_REGEX: Regex = Regex::new(r"^(\d+)\s+(\d+)\s+(\d+)$").unwrap();
+}
 
 impl Almanac {
-    pub fn parse_from_stream(input: &mut impl Read) -> Result<Almanac, AlmanacParseError> {
+    pub fn parse_from_stream(input: &mut impl Read) -> Result<Almanac, Error> {
         let mut almanac = Almanac {
             seed_ranges: vec![],
             connections: vec![],
         };
 
-        let reader = BufReader::new(input);
-
-        for line_result in reader.lines() {
-            let line = line_result.map_err(AlmanacParseError::IoError)?;
+        for (i, line) in read_lines(input)?.into_iter().enumerate() {
+            let located = |k| ParseError::new(k).at_line(i, &line);
 
             if line.is_empty() {
                 continue;
@@ -74,9 +116,7 @@ impl Almanac {
                     almanac.seed_ranges = seeds;
                     continue;
                 } else {
-                    return Err(AlmanacParseError::FormError(
-                        "Expected seed list".to_string(),
-                    ));
+                    return Err(located(ParseErrorKind::Missing("seed list".to_owned())).into());
                 }
             }
 
@@ -87,30 +127,37 @@ impl Almanac {
 
             let last_conn = almanac.connections.last_mut();
             if last_conn.is_none() {
-                return Err(AlmanacParseError::FormError(
-                    "Expected connection header".to_string(),
-                ));
+                return Err(
+                    located(ParseErrorKind::Missing("connection header".to_owned())).into(),
+                );
             }
 
             if let Some(ex) = Self::parse_exception(&line) {
+                // ends past u64::MAX would overflow when checking or shifting values
+                if ex.src.checked_add(ex.count).is_none() || ex.dst.checked_add(ex.count).is_none()
+                {
+                    return Err(located(ParseErrorKind::Invalid(
+                        "Connection exception range does not fit in 64 bits".to_owned(),
+                    ))
+                    .into());
+                }
                 last_conn.unwrap().exceptions.push(ex);
             } else {
-                return Err(AlmanacParseError::FormError(
-                    "Expected connection exception".to_string(),
-                ));
+                return Err(located(ParseErrorKind::Invalid(
+                    "Expected connection exception".to_owned(),
+                ))
+                .into());
             }
         }
 
         if almanac.seed_ranges.is_empty() {
-            return Err(AlmanacParseError::FormError(
-                "Seeds ranges empty".to_string(),
-            ));
+            return Err(ParseError::new(ParseErrorKind::EmptyInput).into());
         }
 
         if almanac.connections.is_empty() {
-            return Err(AlmanacParseError::FormError(
-                "Connections empty".to_string(),
-            ));
+            return Err(
+                ParseError::new(ParseErrorKind::Missing("connection maps".to_owned())).into(),
+            );
         }
 
         Ok(almanac)
@@ -137,10 +184,10 @@ impl Almanac {
                 curr
             };
 
-            println!("{} {} -> {} {}", c.src, curr, c.dst, next);
+            trace!(from = %c.src, to = %c.dst, curr, next, "propagated");
             curr = next
         }
-        println!("Found location {}\n", curr);
+        trace!(location = curr, "found location");
         curr
     }
 
@@ -160,10 +207,10 @@ impl Almanac {
                 curr
             };
 
-            println!("{} {} -> {} {}", c.dst, curr, c.src, prev);
+            trace!(from = %c.dst, to = %c.src, curr, prev, "propagated back");
             curr = prev
         }
-        println!("Found seed {}\n", curr);
+        trace!(seed = curr, "found seed");
         curr
     }
 
@@ -176,7 +223,7 @@ impl Almanac {
         let loc_conn = self.connections.last().unwrap();
 
         let mut exceptions = loc_conn.exceptions.clone();
-        exceptions.sort_by(|a, b| b.dst.cmp(&a.dst));
+        exceptions.sort_by_key(|e| std::cmp::Reverse(e.dst));
 
         let last_ex = exceptions.first().unwrap();
         let max_loc = last_ex.dst + last_ex.count;
@@ -194,7 +241,6 @@ impl Almanac {
             return None;
         }
 
-        println!("------- Building keypoints -------");
         let mut key_points: Vec<u64> = vec![];
 
         let loc_key_points: Vec<u64> = self
@@ -209,7 +255,7 @@ impl Almanac {
         key_points.push(0); // ensure 0 is in.
         key_points.extend(loc_key_points); // add the location connection's native ranges
 
-        println!("Added native location key points: {:?}", key_points);
+        debug!(?key_points, "added native location key points");
 
         // propagate each connection's key points forwards to locations
         // to find key locations at the end
@@ -218,7 +264,7 @@ impl Almanac {
             .iter()
             .enumerate()
             .flat_map(|(i, c)| {
-                println!("------- Find projections from {} -------", c.src);
+                debug!(from = %c.src, "finding projections");
                 let mut points: Vec<_> = c
                     .exceptions
                     .iter()
@@ -238,7 +284,7 @@ impl Almanac {
         key_points.sort_unstable();
         key_points.dedup();
 
-        println!("------- Checking seed ranges -------");
+        debug!(count = key_points.len(), "checking seed ranges");
         key_points
             .windows(2)
             .flat_map(|w| {
@@ -246,9 +292,10 @@ impl Almanac {
                 let seed_end = self.find_seed(w[1]);
 
                 self.seed_ranges.iter().filter_map(move |r| {
-                    let intersection = seed_start.max(r.start)..seed_end.min(r.start + r.count);
+                    let intersection =
+                        seed_start.max(r.start)..seed_end.min(r.start.saturating_add(r.count));
                     if intersection.end > intersection.start {
-                        println!("Found intersecting range {:?}", intersection);
+                        debug!(?intersection, "found intersecting range");
                         Some(vec![intersection.start, intersection.end])
                     } else {
                         None
@@ -260,14 +307,38 @@ impl Almanac {
             .min()
     }
 
+    /// Carries the seed ranges through every connection as ranges, so the work grows with the
+    /// number of intervals rather than with the number of seeds or locations.
+    pub fn closest_seed_loc_intervals(&self) -> Option<u64> {
+        let seeds = self
+            .seed_ranges
+            .iter()
+            .map(|r| r.start..r.start.saturating_add(r.count))
+            .collect();
+
+        let locations = self.connections.iter().fold(merge(seeds), |ranges, c| {
+            let mapped = c.map_ranges(&ranges);
+            debug!(from = %c.src, to = %c.dst, ranges = mapped.len(), "mapped ranges");
+            mapped
+        });
+
+        locations.first().map(|r| r.start)
+    }
+
     fn parse_seeds(line: &str) -> Option<Vec<SeedRange>> {
         let cap = SEEDS_REGEX.captures(line)?;
+        let nums = cap[1]
+            .split_whitespace()
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()?;
+
+        // seeds come in start and count pairs
+        if nums.len() % 2 != 0 {
+            return None;
+        }
+
         Some(
-            cap[1]
-                .split(' ')
-                .map(|part| part.trim().parse::<u64>().unwrap())
-                .collect::<Vec<u64>>()
-                .chunks(2)
+            nums.chunks(2)
                 .map(|c| SeedRange {
                     start: c[0],
                     count: c[1],
@@ -288,9 +359,9 @@ impl Almanac {
     fn parse_exception(line: &str) -> Option<ConnectionException> {
         let cap = CONNECTION_EXCEPTION_REGEX.captures(line)?;
         Some(ConnectionException {
-            dst: cap[1].parse().unwrap(),
-            src: cap[2].parse().unwrap(),
-            count: cap[3].parse().unwrap(),
+            dst: cap[1].parse().ok()?,
+            src: cap[2].parse().ok()?,
+            count: cap[3].parse().ok()?,
         })
     }
 }
@@ -298,6 +369,7 @@ impl Almanac {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
     use stringreader::StringReader;
 
     lazy_static! {
@@ -351,4 +423,93 @@ humidity-to-location map:
 
         assert_eq!(46, closest_loc);
     }
+
+    #[test]
+    fn integration_intervals() {
+        let a = Almanac::parse_from_stream(&mut StringReader::new(&INPUT)).unwrap();
+
+        assert_eq!(Some(46), a.closest_seed_loc_intervals());
+    }
+
+    #[rstest]
+    #[case::example(&INPUT)]
+    #[case::overlapping(
+        "seeds: 5 30 60 5
+
+seed-to-soil map:
+100 10 10
+200 5 10
+0 60 10
+70 62 2
+
+soil-to-location map:
+0 100 3
+40 0 5"
+    )]
+    fn intervals_match_every_seed(#[case] input: &str) {
+        let a = Almanac::parse_from_stream(&mut StringReader::new(input)).unwrap();
+
+        let brute = a
+            .seed_ranges
+            .iter()
+            .flat_map(|r| r.start..r.start + r.count)
+            .map(|s| a.find_location(s))
+            .min();
+
+        assert_eq!(brute, a.closest_seed_loc_intervals());
+    }
+
+    #[test]
+    fn map_ranges_splits_at_boundaries() {
+        let c = Connection {
+            src: "a".to_owned(),
+            dst: "b".to_owned(),
+            exceptions: vec![
+                ConnectionException {
+                    src: 20,
+                    dst: 0,
+                    count: 5,
+                },
+                ConnectionException {
+                    src: 10,
+                    dst: 100,
+                    count: 5,
+                },
+            ],
+        };
+
+        // 8..10 and 15..20 stay, 10..15 moves up by 90 and 20..22 moves down to 0
+        let range = 8..22;
+        assert_eq!(vec![0..2, 8..10, 15..20, 100..105], c.map_ranges(&[range]));
+        assert_eq!(vec![3..5, 30..40], c.map_ranges(&[23..25, 30..40]));
+    }
+
+    #[test]
+    fn map_ranges_first_exception_wins() {
+        let c = Connection {
+            src: "a".to_owned(),
+            dst: "b".to_owned(),
+            exceptions: vec![
+                ConnectionException {
+                    src: 10,
+                    dst: 100,
+                    count: 10,
+                },
+                ConnectionException {
+                    src: 5,
+                    dst: 200,
+                    count: 10,
+                },
+            ],
+        };
+
+        // 10..15 is covered by both and goes with the first, leaving 5..10 to the second
+        let range = 0..20;
+        assert_eq!(vec![0..5, 100..110, 200..205], c.map_ranges(&[range]));
+    }
+
+    #[test]
+    fn merge_ranges() {
+        assert_eq!(vec![0..5, 6..9], merge(vec![6..7, 3..5, 0..3, 7..9, 4..4]));
+    }
 }
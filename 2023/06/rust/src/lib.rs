@@ -0,0 +1,128 @@
+pub mod part1;
+pub mod part2;
+
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind, Solution};
+use std::io::Read;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 6;
+
+    type Input = (Vec<Vec<u64>>, Vec<Vec<u64>>);
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        let lines = read_lines(input)?;
+        let lines: Vec<_> = lines
+            .iter()
+            .map(|l| l.as_str())
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .collect();
+
+        match lines[..] {
+            [_, _] => {}
+            [_, _, (i, extra), ..] => {
+                return Err(ParseError::invalid("Expected 2 lines")
+                    .at_line(i, extra)
+                    .into())
+            }
+            _ => {
+                return Err(
+                    ParseError::new(ParseErrorKind::Missing("distance line".to_owned())).into(),
+                )
+            }
+        }
+
+        let nums1 = part1::parse_nums(lines.clone())?;
+        let nums2 = part2::parse_nums(lines)?;
+
+        Ok((nums1, nums2))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(calculate_ways_to_beat_record(input.0.clone()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(calculate_ways_to_beat_record(input.1.clone()))
+    }
+}
+
+/// Char column of `token`, which has to be a slice of `line`.
+pub(crate) fn column(line: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count()
+}
+
+pub fn calculate_ways_to_beat_record(nums: Vec<Vec<u64>>) -> u32 {
+    let n_ways: u32 = nums[0]
+        .clone()
+        .into_iter()
+        .zip(nums[1].clone())
+        .map(|t| {
+            let time = t.0 as f64;
+            let dist = t.1 as f64;
+
+            let delta = time.powf(2.0) - 4.0 * dist;
+            if delta < 0.0 {
+                return 0;
+            }
+            let sqrt_delta = delta.sqrt();
+
+            // the record has to be beaten, so exact roots only tie and do not count
+            let min = ((time - sqrt_delta) / 2.0).floor() as i64 + 1;
+            let max = ((time + sqrt_delta) / 2.0).ceil() as i64 - 1;
+
+            if max < min {
+                0
+            } else {
+                (max - min + 1) as u32
+            }
+        })
+        .product();
+    n_ways
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Time: 7 15 30\nDistance: 9 40 200", 288)]
+    #[case("Time: 30\nDistance: 200", 9)]
+    #[case("Time: 4\nDistance: 4", 0)]
+    #[case("Time: 3\nDistance: 10", 0)]
+    #[case("Time: 7 4\nDistance: 9 4", 0)]
+    fn ways_to_beat_record(#[case] input: &str, #[case] expected: u32) {
+        let (nums, _) = Day06::parse(input.as_bytes()).unwrap();
+        assert_eq!(expected, calculate_ways_to_beat_record(nums));
+    }
+
+    #[rstest]
+    #[case(
+        "Time: 7 x\nDistance: 9 4",
+        "invalid digit found in string at line 1, column 9"
+    )]
+    #[case(
+        "Time: 7 1\n\nDistance: 9 -4",
+        "invalid digit found in string at line 3, column 13"
+    )]
+    #[case("Time: 7\nDistance: 9\nTime: 3", "Expected 2 lines at line 3")]
+    #[case(
+        "Time: 7 15 30\nDistance: 9 40",
+        "Expected 3 values, found 2 at line 2, column 15"
+    )]
+    #[case(
+        "Time: 7 15\nDistance: 9 40 200",
+        "Expected 2 values, found 3 at line 2, column 16"
+    )]
+    fn located(#[case] input: &str, #[case] expected: &str) {
+        let e = Day06::parse(input.as_bytes()).unwrap_err();
+        assert!(e.to_string().contains(expected), "{e}");
+    }
+}
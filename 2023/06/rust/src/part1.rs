@@ -1,16 +1,32 @@
-use std::num::ParseIntError;
+use crate::column;
+use aoc_common::ParseError;
 
-#[allow(unused)]
-pub fn parse_nums(lines: Vec<&str>) -> Result<Vec<Vec<u64>>, ParseIntError> {
-    let num_results: Result<Vec<Vec<u64>>, _> = lines
+/// Numbers of the (0-based line, line) pairs, one per race, so every line needs as many.
+pub fn parse_nums(lines: Vec<(usize, &str)>) -> Result<Vec<Vec<u64>>, ParseError> {
+    let mut races = None;
+
+    lines
         .into_iter()
-        .map(|l| {
-            l.split_whitespace()
-                .skip(1)
-                .map(|s| s.parse::<u64>())
+        .map(|(i, l)| {
+            let tokens: Vec<&str> = l.split_whitespace().skip(1).collect();
+
+            let expected = *races.get_or_insert(tokens.len());
+            if tokens.len() != expected {
+                // point at the first surplus value, or past the end when values are missing
+                let col = tokens
+                    .get(expected)
+                    .map_or(l.chars().count(), |s| column(l, s));
+                let message = format!("Expected {expected} values, found {}", tokens.len());
+                return Err(ParseError::invalid(message).at(i, col, l));
+            }
+
+            tokens
+                .into_iter()
+                .map(|s| {
+                    s.parse::<u64>()
+                        .map_err(|e| ParseError::invalid(e).at(i, column(l, s), l))
+                })
                 .collect()
         })
-        .collect();
-
-    num_results
+        .collect()
 }
@@ -1,22 +1,20 @@
-use std::num::ParseIntError;
+use crate::column;
+use aoc_common::ParseError;
 
-pub fn parse_nums(lines: Vec<&str>) -> Result<Vec<Vec<u64>>, ParseIntError> {
-    let num_results: Result<Vec<Vec<u64>>, _> = lines
+/// The numbers of the (0-based line, line) pairs with their spaces removed, as a single race.
+pub fn parse_nums(lines: Vec<(usize, &str)>) -> Result<Vec<Vec<u64>>, ParseError> {
+    lines
         .into_iter()
-        .map(|l| {
-            let res = l
-                .split_whitespace()
-                .skip(1)
-                .collect::<Vec<&str>>()
-                .join("")
-                .parse::<u64>();
+        .map(|(i, l)| {
+            let parts: Vec<&str> = l.split_whitespace().skip(1).collect();
 
-            match res {
+            match parts.join("").parse::<u64>() {
                 Ok(num) => Ok(vec![num]),
-                Err(e) => Err(e),
+                Err(e) => {
+                    let col = parts.first().map_or(l.chars().count(), |s| column(l, s));
+                    Err(ParseError::invalid(e).at(i, col, l))
+                }
             }
         })
-        .collect();
-
-    num_results
+        .collect()
 }
@@ -0,0 +1,52 @@
+pub mod part1;
+pub mod part2;
+pub mod poker;
+
+use aoc_common::{read_lines, Error, ParseError, Solution};
+use std::io::Read;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 7;
+
+    type Input = Vec<(poker::Hand, u32)>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        read_lines(input)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, l)| {
+                let located = |e| ParseError::invalid(e).at_line(i, &l);
+
+                let [hand, bid] = l.split_whitespace().collect::<Vec<_>>()[..] else {
+                    return Err(located(
+                        "Input lines are expected to be in form similar to \"32T3K 765\""
+                            .to_owned(),
+                    )
+                    .into());
+                };
+
+                let hand = hand
+                    .parse::<poker::Hand>()
+                    .map_err(|e| located(e.to_string()))?;
+                let bid = bid.parse::<u32>().map_err(|e| located(e.to_string()))?;
+
+                Ok((hand, bid))
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        let plays = input.iter().map(|&(h, b)| (part1::Hand(h), b)).collect();
+        Ok(poker::total_winnings(plays))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        let plays = input.iter().map(|&(h, b)| (part2::Hand(h), b)).collect();
+        Ok(poker::total_winnings(plays))
+    }
+}
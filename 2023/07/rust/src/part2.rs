@@ -1,31 +1,40 @@
-use crate::poker::{Card, ComparableHand, Hand, HandType};
+use crate::poker::{self, Card, ComparableHand, HandParseError, HandType};
 use std::collections::HashMap;
+use std::str::FromStr;
 
-impl Card {
-    pub fn value(&self) -> u8 {
-        match self {
-            Card::J => 1,
-            Card::Num(n) => *n,
-            Card::T => 10,
-            Card::Q => 12,
-            Card::K => 13,
-            Card::A => 14,
-        }
+pub struct Hand(pub poker::Hand);
+
+impl FromStr for Hand {
+    type Err = HandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Hand)
+    }
+}
+
+pub fn value(card: &Card) -> u8 {
+    match card {
+        Card::J => 1,
+        Card::Num(n) => *n,
+        Card::T => 10,
+        Card::Q => 12,
+        Card::K => 13,
+        Card::A => 14,
     }
 }
 
 impl ComparableHand for Hand {
     fn cards(&self) -> [Card; 5] {
-        self.cards
+        self.0.cards
     }
 
     fn hand_type(&self) -> HandType {
-        let count_map = self.cards.iter().fold(HashMap::new(), |mut acc, c| {
-            *acc.entry(c.value()).or_insert(0) += 1;
+        let count_map = self.0.cards.iter().fold(HashMap::new(), |mut acc, c| {
+            *acc.entry(value(c)).or_insert(0) += 1;
             acc
         });
 
-        let joker_count = count_map.get(&Card::J.value()).unwrap_or(&0);
+        let joker_count = count_map.get(&value(&Card::J)).unwrap_or(&0);
 
         if joker_count == &5 {
             return HandType::FiveOfAKind;
@@ -33,7 +42,7 @@ impl ComparableHand for Hand {
 
         let filtered_map: HashMap<&u8, &i32> = count_map
             .iter()
-            .filter(|&(key, _)| key != &Card::J.value())
+            .filter(|&(key, _)| key != &value(&Card::J))
             .collect();
 
         let mut card_counts: Vec<i32> = filtered_map.values().map(|i| **i).collect();
@@ -51,6 +60,10 @@ impl ComparableHand for Hand {
             _ => HandType::HighCard,
         }
     }
+
+    fn card_value(&self, card: &Card) -> u8 {
+        value(card)
+    }
 }
 
 #[cfg(test)]
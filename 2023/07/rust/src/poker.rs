@@ -7,8 +7,10 @@ use std::str::FromStr;
 pub trait ComparableHand {
     fn cards(&self) -> [Card; 5];
     fn hand_type(&self) -> HandType;
+    fn card_value(&self, card: &Card) -> u8;
 }
 
+#[derive(Clone, Copy)]
 pub struct Hand {
     pub cards: [Card; 5],
 }
@@ -24,8 +26,8 @@ pub enum HandType {
     FiveOfAKind,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum Card {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Card {
     Num(u8),
     T,
     J,
@@ -48,26 +50,6 @@ impl Card {
     }
 }
 
-impl PartialEq<Self> for Card {
-    fn eq(&self, other: &Self) -> bool {
-        self.value() == other.value()
-    }
-}
-
-impl Eq for Card {}
-
-impl PartialOrd for Card {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Card {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.value().cmp(&other.value())
-    }
-}
-
 impl PartialEq<Self> for dyn ComparableHand {
     fn eq(&self, other: &Self) -> bool {
         self.cards().eq(&other.cards())
@@ -91,13 +73,23 @@ impl Ord for dyn ComparableHand {
                 .cards()
                 .iter()
                 .zip(&other.cards())
-                .map(|(c1, c2)| c1.cmp(c2))
+                .map(|(c1, c2)| self.card_value(c1).cmp(&other.card_value(c2)))
                 .find(|o| !o.is_eq())
                 .unwrap_or(Ordering::Equal),
         }
     }
 }
 
+pub fn total_winnings<H: ComparableHand + 'static>(mut plays: Vec<(H, u32)>) -> u32 {
+    plays.sort_unstable_by(|a, b| (&a.0 as &dyn ComparableHand).cmp(&b.0));
+
+    plays
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| p.1 * (i as u32 + 1))
+        .sum()
+}
+
 #[derive(Debug)]
 pub struct UnknownCardError {
     card: char,
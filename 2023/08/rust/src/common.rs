@@ -1,6 +1,6 @@
+use aoc_common::{ParseError, ParseErrorKind};
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
 pub(crate) struct Steps(pub(crate) Vec<Step>);
@@ -23,7 +23,7 @@ lazy_static! {
 }
 
 impl FromStr for Node {
-    type Err = LineParseError;
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match NODE_REGEX.captures(s) {
@@ -32,21 +32,23 @@ impl FromStr for Node {
                 left: caps[2].to_string(),
                 right: caps[3].to_string(),
             }),
-            None => Err(LineParseError::InvalidNodeLine),
+            None => Err(ParseErrorKind::Invalid("Invalid node line".to_owned())),
         }
     }
 }
 
 impl FromStr for Steps {
-    type Err = LineParseError;
+    /// Located on the first line, where the steps are.
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let steps: Vec<Step> = s
             .chars()
-            .map(|c| match c {
+            .enumerate()
+            .map(|(i, c)| match c {
                 'L' => Ok(Step::Left),
                 'R' => Ok(Step::Right),
-                _ => Err(LineParseError::UnknownStepCharacter(c)),
+                _ => Err(ParseError::new(ParseErrorKind::UnknownCharacter(c)).at(0, i, s)),
             })
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -54,23 +56,6 @@ impl FromStr for Steps {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub enum LineParseError {
-    UnknownStepCharacter(char),
-    InvalidNodeLine,
-}
-
-impl Display for LineParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            LineParseError::UnknownStepCharacter(c) => write!(f, "unknown step character: '{}'", c),
-            LineParseError::InvalidNodeLine => write!(f, "invalid node line"),
-        }
-    }
-}
-
-impl std::error::Error for LineParseError {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
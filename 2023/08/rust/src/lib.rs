@@ -0,0 +1,63 @@
+pub mod common;
+pub mod part1;
+pub mod part2;
+
+use aoc_common::{Error, ParseError, Solution};
+use std::io::Read;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
+
+    type Input = (Result<part1::Map, ParseError>, part2::Map);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(mut input: impl Read) -> Result<Self::Input, Error> {
+        let mut buf = vec![];
+        input.read_to_end(&mut buf)?;
+
+        // part 1 needs AAA and ZZZ which the part 2 examples do not have
+        let map1 = match part1::Map::parse_from_stream(&buf[..]) {
+            Ok(map) => Ok(map),
+            Err(Error::Parse(e)) => Err(e),
+            Err(e) => return Err(e),
+        };
+        let map2 = part2::Map::parse_from_stream(&buf[..])?;
+
+        Ok((map1, map2))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        match &input.0 {
+            Ok(map) => Ok(map.count_steps()),
+            Err(e) => Err(e.clone().into()),
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        input
+            .1
+            .calculate_steps()
+            .map_err(|e| Error::Solve(e.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("LRX\n\nAAA = (ZZZ, ZZZ)", "Unknown character 'X' at line 1, column 3")]
+    #[case("\n\nAAA = (ZZZ, ZZZ)", "Missing steps at line 1")]
+    #[case("LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = ZZZ", "Invalid node line at line 4")]
+    fn located(#[case] input: &str, #[case] expected: &str) {
+        let Err(e) = Day08::parse(input.as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert!(e.to_string().contains(expected), "{e}");
+    }
+}
@@ -1,7 +1,6 @@
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind};
 use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
-use std::io::{BufRead, BufReader, Read};
-use std::{fmt, io};
+use std::io::Read;
 
 use crate::common::{Node, Step, Steps};
 
@@ -11,44 +10,38 @@ pub struct Map {
 }
 
 impl Map {
-    pub fn parse_from_stream(input: impl Read) -> Result<Map, MapParseError> {
-        let reader = BufReader::new(input);
-        let mut lines = reader.lines();
+    pub fn parse_from_stream(input: impl Read) -> Result<Map, Error> {
+        let mut lines = read_lines(input)?.into_iter().enumerate();
 
         let first_line = match lines.next() {
-            Some(Ok(line)) => line,
-            Some(Err(e)) => return Err(MapParseError::IO(e)),
-            None => return Err(MapParseError::ExpectedStepsLine),
+            Some((_, line)) => line,
+            None => return Err(ParseError::new(ParseErrorKind::EmptyInput).into()),
         };
 
-        let steps = first_line
-            .parse::<Steps>()
-            .map_err(|_| MapParseError::ExpectedStepsLine)?
-            .0;
+        let steps = first_line.parse::<Steps>()?.0;
 
         if steps.is_empty() {
-            return Err(MapParseError::ExpectedStepsLine);
+            let missing = ParseErrorKind::Missing("steps".to_owned());
+            return Err(ParseError::new(missing).at_line(0, &first_line).into());
         }
 
         let nodes: HashMap<String, Node> = lines
             .skip(1)
-            .map(|l| match l {
-                Ok(line) => line
-                    .parse::<Node>()
-                    .map_err(|_| MapParseError::ExpectedNodeLine),
-                Err(e) => Err(MapParseError::IO(e)),
+            .map(|(i, line)| {
+                line.parse::<Node>()
+                    .map_err(|k| ParseError::new(k).at_line(i, &line).into())
             })
-            .collect::<Result<Vec<_>, _>>()?
+            .collect::<Result<Vec<_>, Error>>()?
             .into_iter()
             .map(|n| (n.key.clone(), n))
             .collect();
 
         if !nodes.contains_key("AAA") {
-            return Err(MapParseError::MissingNode(String::from("AAA")));
+            return Err(missing("node AAA"));
         }
 
         if !nodes.contains_key("ZZZ") {
-            return Err(MapParseError::MissingNode(String::from("ZZZ")));
+            return Err(missing("node ZZZ"));
         }
 
         Ok(Map { steps, nodes })
@@ -73,27 +66,10 @@ impl Map {
     }
 }
 
-#[derive(Debug)]
-pub enum MapParseError {
-    IO(io::Error),
-    ExpectedStepsLine,
-    ExpectedNodeLine,
-    MissingNode(String),
+fn missing(what: &str) -> Error {
+    ParseError::new(ParseErrorKind::Missing(what.to_owned())).into()
 }
 
-impl Display for MapParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            MapParseError::IO(e) => write!(f, "error while reading input: {}", e),
-            MapParseError::ExpectedStepsLine => write!(f, "expected steps line"),
-            MapParseError::ExpectedNodeLine => write!(f, "expected node line"),
-            MapParseError::MissingNode(s) => write!(f, "missing node: {}", s),
-        }
-    }
-}
-
-impl std::error::Error for MapParseError {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
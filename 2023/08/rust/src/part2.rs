@@ -1,9 +1,8 @@
 use crate::common::{Node, Step, Steps};
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind};
 use std::collections::{HashMap, HashSet};
-use std::fmt::{Display, Formatter};
-use std::hash::Hash;
-use std::io::{BufRead, BufReader, Read};
-use std::{fmt, io};
+use std::io::Read;
+use tracing::debug;
 
 pub struct Map {
     steps: Vec<Step>,
@@ -85,34 +84,28 @@ impl Map {
         key.ends_with('Z')
     }
 
-    pub fn parse_from_stream(input: impl Read) -> Result<Map, MapParseError> {
-        let reader = BufReader::new(input);
-        let mut lines = reader.lines();
+    pub fn parse_from_stream(input: impl Read) -> Result<Map, Error> {
+        let mut lines = read_lines(input)?.into_iter().enumerate();
 
         let first_line = match lines.next() {
-            Some(Ok(line)) => line,
-            Some(Err(e)) => return Err(MapParseError::IO(e)),
-            None => return Err(MapParseError::ExpectedStepsLine),
+            Some((_, line)) => line,
+            None => return Err(ParseError::new(ParseErrorKind::EmptyInput).into()),
         };
 
-        let steps = first_line
-            .parse::<Steps>()
-            .map_err(|_| MapParseError::ExpectedStepsLine)?
-            .0;
+        let steps = first_line.parse::<Steps>()?.0;
 
         if steps.is_empty() {
-            return Err(MapParseError::ExpectedStepsLine);
+            let missing = ParseErrorKind::Missing("steps".to_owned());
+            return Err(ParseError::new(missing).at_line(0, &first_line).into());
         }
 
         let nodes: HashMap<String, Node> = lines
             .skip(1)
-            .map(|l| match l {
-                Ok(line) => line
-                    .parse::<Node>()
-                    .map_err(|_| MapParseError::ExpectedNodeLine),
-                Err(e) => Err(MapParseError::IO(e)),
+            .map(|(i, line)| {
+                line.parse::<Node>()
+                    .map_err(|k| ParseError::new(k).at_line(i, &line).into())
             })
-            .collect::<Result<Vec<_>, _>>()?
+            .collect::<Result<Vec<_>, Error>>()?
             .into_iter()
             .map(|n| (n.key.clone(), n))
             .collect();
@@ -124,7 +117,7 @@ impl Map {
             .collect();
 
         if start_keys.is_empty() {
-            return Err(MapParseError::MissingStartNode);
+            return Err(missing("start node"));
         }
 
         let end_keys: HashSet<_> = nodes
@@ -134,7 +127,7 @@ impl Map {
             .collect();
 
         if end_keys.is_empty() {
-            return Err(MapParseError::MissingEndNode);
+            return Err(missing("end node"));
         }
 
         Ok(Map {
@@ -170,7 +163,7 @@ impl Map {
         // find loops for each starting point and note down stops
         let loops = self.find_loops();
 
-        println!("{:?}", loops);
+        debug!(?loops, "found loops");
 
         // this is actually an under-determined system of linear equations
         // number of starting points n - 1 equations & n variables (it's periodic)
@@ -216,7 +209,7 @@ impl Map {
             return Err("input is not well-aligned with assumptions");
         }
 
-        Ok(loops.iter().fold(1, |mut acc, l| Self::lcm(acc, l.length)))
+        Ok(loops.iter().fold(1, |acc, l| Self::lcm(acc, l.length)))
     }
 
     fn find_loops(&self) -> Vec<Loop> {
@@ -261,29 +254,10 @@ impl Map {
     }
 }
 
-#[derive(Debug)]
-pub enum MapParseError {
-    IO(io::Error),
-    ExpectedStepsLine,
-    ExpectedNodeLine,
-    MissingStartNode,
-    MissingEndNode,
-}
-
-impl Display for MapParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            MapParseError::IO(e) => write!(f, "error while reading input: {}", e),
-            MapParseError::ExpectedStepsLine => write!(f, "expected steps line"),
-            MapParseError::ExpectedNodeLine => write!(f, "expected node line"),
-            MapParseError::MissingStartNode => write!(f, "there are no start nodes"),
-            MapParseError::MissingEndNode => write!(f, "there are no end nodes"),
-        }
-    }
+fn missing(what: &str) -> Error {
+    ParseError::new(ParseErrorKind::Missing(what.to_owned())).into()
 }
 
-impl std::error::Error for MapParseError {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,49 @@
+pub mod series;
+
+use aoc_common::{read_lines, Error, ParseError, Solution};
+use series::Series;
+use std::io::Read;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 9;
+
+    type Input = Vec<Series>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        read_lines(input)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, l)| {
+                Ok(l.parse::<Series>()
+                    .map_err(|e| ParseError::invalid(e).at_line(i, &l))?)
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(input
+            .iter()
+            .map(|s| {
+                let mut s = s.clone();
+                s.extrapolate_forward();
+                *s.levels[0].last().unwrap()
+            })
+            .sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input
+            .iter()
+            .map(|s| {
+                let mut s = s.clone();
+                s.extrapolate_backwards();
+                *s.levels[0].first().unwrap()
+            })
+            .sum())
+    }
+}
@@ -0,0 +1,33 @@
+pub mod map;
+
+use aoc_common::{Error, Position, Solution};
+use map::Map;
+use std::io::Read;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 10;
+
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        Map::from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(find_loop(input)?.len() / 2)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input.find_nests(find_loop(input)?).len())
+    }
+}
+
+fn find_loop(map: &Map) -> Result<Vec<Position>, Error> {
+    map.find_loop_iteration()
+        .ok_or(Error::Solve("No loop found in map".to_owned()))
+}
@@ -1,29 +1,12 @@
+use aoc_common::{Direction, Error, Grid, ParseError, ParseErrorKind, Position};
 use lazy_static::lazy_static;
 use std::collections::{HashMap, HashSet};
-use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
+use std::io::Read;
 
 lazy_static! {
     static ref PIPES: HashMap<char, Vec<Direction>> = {
         let mut m = HashMap::new();
-        m.insert(
-            'S',
-            vec![
-                Direction::North,
-                Direction::East,
-                Direction::South,
-                Direction::West,
-            ],
-        );
+        m.insert('S', Direction::ALL.to_vec());
         m.insert('.', vec![]);
         m.insert('|', vec![Direction::North, Direction::South]);
         m.insert('-', vec![Direction::East, Direction::West]);
@@ -37,7 +20,7 @@ lazy_static! {
 
 #[derive(Debug, PartialEq)]
 struct Node {
-    pos: (i64, i64),
+    pos: Position,
     kind: char,
 }
 
@@ -47,23 +30,25 @@ impl Node {
     }
 
     fn is_adjacent(&self, other: &Node) -> bool {
-        (self.pos.0.abs_diff(other.pos.0) + self.pos.1.abs_diff(other.pos.1)) == 1
+        self.pos.manhattan(&other.pos) == 1
     }
 
     fn can_connect(&self, other: &Node) -> bool {
         let s = PIPES.get(&self.kind).unwrap();
         let o = PIPES.get(&other.kind).unwrap();
 
-        (self.pos.0 > other.pos.0 && s.contains(&Direction::West) && o.contains(&Direction::East))
-            || (self.pos.0 < other.pos.0
-                && s.contains(&Direction::East)
-                && o.contains(&Direction::West))
-            || (self.pos.1 > other.pos.1
-                && s.contains(&Direction::North)
-                && o.contains(&Direction::South))
-            || (self.pos.1 < other.pos.1
-                && s.contains(&Direction::South)
-                && o.contains(&Direction::North))
+        s.iter()
+            .filter(|d| self.faces(other, **d))
+            .any(|d| o.contains(&d.reverse()))
+    }
+
+    fn faces(&self, other: &Node, dir: Direction) -> bool {
+        match dir {
+            Direction::North => other.pos.y < self.pos.y,
+            Direction::East => other.pos.x > self.pos.x,
+            Direction::South => other.pos.y > self.pos.y,
+            Direction::West => other.pos.x < self.pos.x,
+        }
     }
 
     fn is_opposite(&self, other: &Node) -> bool {
@@ -76,71 +61,48 @@ impl Node {
 
 #[derive(Debug, PartialEq)]
 pub struct Map {
-    start: (i64, i64),
-    nodes: HashMap<(i64, i64), Node>,
+    start: Position,
+    nodes: Grid<Node>,
 }
 
 impl Map {
-    pub fn from_stream(s: impl Read) -> Result<Map, ParseError> {
-        let reader = BufReader::new(s);
-
-        let lines: Vec<(usize, String)> = reader
-            .lines()
-            .enumerate()
-            .map(|(i, l)| {
-                let line = l.map_err(ParseError::IO)?;
-                Ok((i, line))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let nodes = Self::parse_nodes(lines)?;
+    pub fn from_stream(s: impl Read) -> Result<Map, Error> {
+        let kinds = Grid::parse(s, |c| match PIPES.contains_key(&c) {
+            true => Ok(c),
+            false => Err(ParseErrorKind::UnknownCharacter(c)),
+        })?;
+
+        let nodes = kinds.map(|pos, &kind| Node {
+            pos: pos.into(),
+            kind,
+        });
+
+        let start = nodes
+            .iter()
+            .find(|(_, n)| n.kind == 'S')
+            .map(|(_, n)| n.pos);
 
-        match nodes.iter().find(|(_, n)| n.kind == 'S') {
-            Some(((x, y), _)) => Ok(Map {
-                start: (*x, *y),
-                nodes,
-            }),
-            None => Err(ParseError::MissingStart),
+        match start {
+            Some(start) => Ok(Map { start, nodes }),
+            None => Err(ParseError::new(ParseErrorKind::Missing("start node".to_owned())).into()),
         }
     }
 
-    fn parse_nodes(lines: Vec<(usize, String)>) -> Result<HashMap<(i64, i64), Node>, ParseError> {
-        let nodes: HashMap<(i64, i64), Node> = lines
-            .into_iter()
-            .flat_map(|(y, s)| {
-                s.chars()
-                    .enumerate()
-                    .map(|(x, c)| {
-                        if !PIPES.contains_key(&c) {
-                            Err(ParseError::UnknownCharacter(c))
-                        } else {
-                            let pos = (x as i64, y as i64);
-                            Ok((pos, Node { pos, kind: c }))
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
-        Ok(nodes)
+    fn node(&self, pos: Position) -> Option<&Node> {
+        self.nodes.get_signed(pos)
     }
 
     fn connecting_nodes(&self, node: &Node) -> Vec<&Node> {
-        let top = self.nodes.get(&(node.pos.0, node.pos.1 - 1));
-        let left = self.nodes.get(&(node.pos.0 - 1, node.pos.1));
-        let bottom = self.nodes.get(&(node.pos.0, node.pos.1 + 1));
-        let right = self.nodes.get(&(node.pos.0 + 1, node.pos.1));
-
-        vec![top, left, bottom, right]
-            .into_iter()
-            .flatten()
+        Direction::all()
+            .filter_map(|d| self.node(node.pos.step(d)))
             .filter(|n| node.is_connecting(n))
             .collect()
     }
 
     // depth-first search to find the loop
     #[allow(unused)]
-    pub fn find_loop_recursive(&self) -> Option<Vec<(i64, i64)>> {
-        let start = self.nodes.get(&self.start).unwrap();
+    pub fn find_loop_recursive(&self) -> Option<Vec<Position>> {
+        let start = self.node(self.start).unwrap();
 
         self.connecting_nodes(start)
             .into_iter()
@@ -149,7 +111,7 @@ impl Map {
     }
 
     #[allow(unused)]
-    fn explore(&self, visited: Vec<(i64, i64)>, node: &Node) -> Option<Vec<(i64, i64)>> {
+    fn explore(&self, visited: Vec<Position>, node: &Node) -> Option<Vec<Position>> {
         if node.kind == 'S' {
             return Some(visited);
         }
@@ -166,11 +128,11 @@ impl Map {
             .next()
     }
 
-    pub fn find_loop_iteration(&self) -> Option<Vec<(i64, i64)>> {
-        let start = self.nodes.get(&self.start).unwrap();
+    pub fn find_loop_iteration(&self) -> Option<Vec<Position>> {
+        let start = self.node(self.start).unwrap();
 
         // Stack for depth-first search (current node, path visited so far)
-        let mut stack: Vec<(&Node, Vec<(i64, i64)>)> = vec![(start, vec![])];
+        let mut stack: Vec<(&Node, Vec<Position>)> = vec![(start, vec![])];
 
         while let Some((node, path)) = stack.pop() {
             if !path.is_empty() && node.kind == 'S' {
@@ -189,29 +151,30 @@ impl Map {
         None
     }
 
-    pub fn find_nests(&self, path: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    pub fn find_nests(&self, path: Vec<Position>) -> Vec<Position> {
         let path_pos: HashSet<_> = path.iter().collect();
         let path_nodes: Vec<_> = path
             .clone()
             .into_iter()
-            .map(|p| self.nodes.get(&p).unwrap())
+            .map(|p| self.node(p).unwrap())
             .collect();
 
         self.nodes
             .iter()
-            .filter(|((x, y), _)| !path_pos.contains(&(*x, *y)))
-            .filter(|((x, y), _)| {
+            .map(|(_, n)| n.pos)
+            .filter(|pos| !path_pos.contains(pos))
+            .filter(|Position { x, y }| {
                 // vertical ray casting from outside
                 let mut v_crosses: Vec<_> = path_nodes
                     .iter()
-                    .filter(|pn| pn.pos.0 == *x && pn.pos.1 < *y)
+                    .filter(|pn| pn.pos.x == *x && pn.pos.y < *y)
                     .filter(|pn| {
                         let pipes = PIPES.get(&pn.kind).unwrap();
                         pipes.contains(&Direction::West) || pipes.contains(&Direction::East)
                     })
                     .collect();
 
-                v_crosses.sort_by(|a,b| a.pos.1.cmp(&b.pos.1));
+                v_crosses.sort_by_key(|pn| pn.pos.y);
 
                 // eliminate double count for opposite connections (L7,FJ)
                 let connected_v_count = v_crosses
@@ -221,14 +184,14 @@ impl Map {
 
                 let mut h_crosses: Vec<_> = path_nodes
                     .iter()
-                    .filter(|pn| pn.pos.0 < *x && pn.pos.1 == *y)
+                    .filter(|pn| pn.pos.x < *x && pn.pos.y == *y)
                     .filter(|pn| {
                         let pipes = PIPES.get(&pn.kind).unwrap();
                         pipes.contains(&Direction::North) || pipes.contains(&Direction::South)
                     })
                     .collect();
 
-                h_crosses.sort_by(|a,b| a.pos.0.cmp(&b.pos.0));
+                h_crosses.sort_by_key(|pn| pn.pos.x);
 
                 let connected_h_count = h_crosses
                     .windows(2)
@@ -238,28 +201,10 @@ impl Map {
                 ((h_crosses.len() - connected_h_count) % 2 == 1)
                     && ((v_crosses.len() - connected_v_count) % 2 == 1)
             })
-            .map(|((x, y), _)| (*x, *y))
             .collect()
     }
 }
 
-#[derive(Debug)]
-pub(crate) enum ParseError {
-    IO(io::Error),
-    UnknownCharacter(char),
-    MissingStart,
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::IO(e) => write!(f, "Could not read file: {}", e),
-            ParseError::UnknownCharacter(c) => write!(f, "Unknown character: {}", c),
-            ParseError::MissingStart => write!(f, "Missing start node"),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,16 +220,16 @@ mod tests {
 
         let map = Map::from_stream(StringReader::new(input)).unwrap();
 
-        assert_eq!((1, 1), map.start);
+        assert_eq!(Position::new(1, 1), map.start);
         assert_eq!(25, map.nodes.len());
         assert_eq!(17, map.nodes.iter().filter(|(_, n)| n.kind == '.').count());
-        assert_eq!('-', map.nodes.get(&(2, 1)).unwrap().kind);
-        assert_eq!('7', map.nodes.get(&(3, 1)).unwrap().kind);
-        assert_eq!('|', map.nodes.get(&(1, 2)).unwrap().kind);
-        assert_eq!('|', map.nodes.get(&(3, 2)).unwrap().kind);
-        assert_eq!('L', map.nodes.get(&(1, 3)).unwrap().kind);
-        assert_eq!('-', map.nodes.get(&(2, 3)).unwrap().kind);
-        assert_eq!('J', map.nodes.get(&(3, 3)).unwrap().kind);
+        assert_eq!('-', map.nodes[(2, 1)].kind);
+        assert_eq!('7', map.nodes[(3, 1)].kind);
+        assert_eq!('|', map.nodes[(1, 2)].kind);
+        assert_eq!('|', map.nodes[(3, 2)].kind);
+        assert_eq!('L', map.nodes[(1, 3)].kind);
+        assert_eq!('-', map.nodes[(2, 3)].kind);
+        assert_eq!('J', map.nodes[(3, 3)].kind);
     }
 
     #[test]
@@ -336,10 +281,10 @@ LJ...";
 
         assert_eq!(4, nests.len());
 
-        assert!(nests.contains(&(2, 6)));
-        assert!(nests.contains(&(3, 6)));
-        assert!(nests.contains(&(7, 6)));
-        assert!(nests.contains(&(8, 6)));
+        assert!(nests.contains(&Position::new(2, 6)));
+        assert!(nests.contains(&Position::new(3, 6)));
+        assert!(nests.contains(&Position::new(7, 6)));
+        assert!(nests.contains(&Position::new(8, 6)));
     }
 
     #[test]
@@ -409,4 +354,23 @@ L7JLJL-JLJLJL--JLJ.L";
 
         assert!(map.is_err());
     }
+
+    #[test]
+    fn unknown_char_location() {
+        let input = ".....
+.S-K.
+.....";
+
+        let Err(Error::Parse(e)) = Map::from_stream(StringReader::new(input)) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(ParseErrorKind::UnknownCharacter('K'), e.kind);
+        assert_eq!(
+            Some((2, 4, ".S-K.")),
+            e.location
+                .as_ref()
+                .map(|l| (l.line, l.column, l.snippet.as_str()))
+        );
+    }
 }
@@ -1,10 +1,10 @@
-use std::collections::{HashMap, HashSet};
-use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use aoc_common::{Error, Grid, ParseErrorKind};
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::io::Read;
 
 pub struct Galaxy {
-    pub stars: HashMap<(i64, i64), Star>,
+    pub stars: Vec<Star>,
     empty_x: HashSet<i64>,
     empty_y: HashSet<i64>,
 }
@@ -15,23 +15,25 @@ pub struct Star {
 }
 
 impl Galaxy {
-    pub fn from_stream(s: impl Read) -> Result<Galaxy, ParseError> {
-        let reader = BufReader::new(s);
-
-        let lines: Vec<(usize, String)> = reader
-            .lines()
-            .enumerate()
-            .map(|(i, l)| {
-                let line = l.map_err(ParseError::IO)?;
-                Ok((i, line))
+    pub fn from_stream(s: impl Read) -> Result<Galaxy, Error> {
+        let space = Grid::parse(s, |c| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            _ => Err(ParseErrorKind::UnknownCharacter(c)),
+        })?;
+
+        let stars = space
+            .iter()
+            .filter(|(_, &star)| star)
+            .map(|((x, y), _)| Star {
+                x: x as i64,
+                y: y as i64,
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect();
 
-        let stars = Self::parse_stars(lines)?;
+        let empty_x = Self::find_empty(space.columns().map(|mut c| c.any(|&star| star)));
 
-        let empty_x = Self::find_empty_x(&stars);
-
-        let empty_y = Self::find_empty_y(&stars);
+        let empty_y = Self::find_empty(space.rows().map(|r| r.contains(&true)));
 
         Ok(Galaxy {
             stars,
@@ -40,75 +42,23 @@ impl Galaxy {
         })
     }
 
-    fn parse_stars(lines: Vec<(usize, String)>) -> Result<HashMap<(i64, i64), Star>, ParseError> {
-        let stars: HashMap<(i64, i64), Star> = lines
-            .into_iter()
-            .flat_map(|(y, s)| {
-                s.chars()
-                    .enumerate()
-                    .filter_map(|(x, c)| match c {
-                        '.' => None,
-                        '#' => Some(Ok((
-                            (x as i64, y as i64),
-                            Star {
-                                x: x as i64,
-                                y: y as i64,
-                            },
-                        ))),
-                        _ => Some(Err(ParseError::UnknownCharacter(c))),
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
-        Ok(stars)
-    }
-
-    fn find_empty_x(stars: &HashMap<(i64, i64), Star>) -> HashSet<i64> {
-        let x_min = stars
-            .keys()
-            .min_by(|a, b| a.0.cmp(&b.0))
-            .unwrap_or(&(0, 0))
-            .0;
-
-        let x_max = stars
-            .keys()
-            .max_by(|a, b| a.0.cmp(&b.0))
-            .unwrap_or(&(0, 0))
-            .0;
-
-        (x_min..x_max)
-            .filter(|x| !stars.keys().any(|(xs, _)| xs == x))
-            .collect()
-    }
-
-    fn find_empty_y(stars: &HashMap<(i64, i64), Star>) -> HashSet<i64> {
-        let y_min = stars
-            .keys()
-            .min_by(|a, b| a.1.cmp(&b.1))
-            .unwrap_or(&(0, 0))
-            .1;
-
-        let y_max = stars
-            .keys()
-            .max_by(|a, b| a.1.cmp(&b.1))
-            .unwrap_or(&(0, 0))
-            .1;
-
-        (y_min..y_max)
-            .filter(|y| !stars.keys().any(|(_, ys)| ys == y))
+    fn find_empty(has_star: impl Iterator<Item = bool>) -> HashSet<i64> {
+        has_star
+            .enumerate()
+            .filter(|(_, star)| !star)
+            .map(|(i, _)| i as i64)
             .collect()
     }
 
     pub fn calculate_total_distance(&self, universe_age: u64) -> u64 {
         self.stars
-            .values()
+            .par_iter()
             .enumerate()
-            .flat_map(|(i1, s1)| {
-                self.stars
-                    .values()
-                    .enumerate()
-                    .filter(move |(i2, _)| i1 < *i2)
-                    .map(|(_, s2)| self.measure_distance(s1, s2, universe_age))
+            .map(|(i1, s1)| {
+                self.stars[i1 + 1..]
+                    .iter()
+                    .map(|s2| self.measure_distance(s1, s2, universe_age))
+                    .sum::<u64>()
             })
             .sum()
     }
@@ -131,21 +81,6 @@ impl Galaxy {
     }
 }
 
-#[derive(Debug)]
-pub(crate) enum ParseError {
-    IO(io::Error),
-    UnknownCharacter(char),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::IO(e) => write!(f, "Could not read file: {}", e),
-            ParseError::UnknownCharacter(c) => write!(f, "Unknown character: {}", c),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,28 @@
+pub mod galaxy;
+
+use aoc_common::{Error, Solution};
+use galaxy::Galaxy;
+use std::io::Read;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 11;
+
+    type Input = Galaxy;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        Galaxy::from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(input.calculate_total_distance(2))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input.calculate_total_distance(1_000_000))
+    }
+}
@@ -38,13 +38,13 @@ impl Record {
         memo: &mut HashMap<String, u64>,
     ) -> u64 {
         let key = Self::encode_inputs(config, seq);
-        return if let Some(res) = memo.get(&key) {
+        if let Some(res) = memo.get(&key) {
             *res
         } else {
             let res = Self::count_configurations(config, seq, memo);
             memo.insert(key, res);
             res
-        };
+        }
     }
 
     fn count_configurations(
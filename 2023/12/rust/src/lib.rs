@@ -0,0 +1,46 @@
+pub mod inventory;
+
+use aoc_common::{read_lines, Error, ParseError, Solution};
+use inventory::Record;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::io::Read;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 12;
+
+    type Input = Vec<Record>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        read_lines(input)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, l)| {
+                Ok(l.parse::<Record>()
+                    .map_err(|e| ParseError::invalid(e).at_line(i, &l))?)
+            })
+            .collect()
+    }
+
+    // every worker keeps its own memo, which only caches and so does not change the counts
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(input
+            .par_iter()
+            .map_init(HashMap::new, |memo, r| r.valid_configuration_count(memo))
+            .sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input
+            .par_iter()
+            .map_init(HashMap::new, |memo, r| {
+                r.unfolded(5).valid_configuration_count(memo)
+            })
+            .sum())
+    }
+}
@@ -0,0 +1,57 @@
+pub mod mirror;
+
+use aoc_common::{read_lines, Error, Solution};
+use either::{Either, Left, Right};
+use mirror::Valley;
+use rayon::prelude::*;
+use std::io::Read;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 13;
+
+    type Input = Vec<Valley>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        let lines = read_lines(input)?;
+
+        let mut valleys = vec![];
+        let mut start = 0;
+
+        for end in (0..=lines.len()).filter(|&i| i == lines.len() || lines[i].is_empty()) {
+            if end > start {
+                let rows: Vec<&str> = lines[start..end].iter().map(|l| l.as_str()).collect();
+                valleys.push(Valley::try_from(&rows[..]).map_err(|e| e.offset(start))?);
+            }
+            start = end + 1;
+        }
+
+        Ok(valleys)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(input
+            .par_iter()
+            .map(|v| summarize(v.mirror_pos(None)))
+            .sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input
+            .par_iter()
+            .map(|v| summarize(v.smudged_mirror_pos()))
+            .sum())
+    }
+}
+
+fn summarize(pos: Option<Either<usize, usize>>) -> usize {
+    match pos {
+        Some(Left(h)) => 100 * h,
+        Some(Right(v)) => v,
+        _ => 0,
+    }
+}
@@ -1,5 +1,5 @@
+use aoc_common::{Grid, ParseError, ParseErrorKind};
 use either::{Either, Left, Right};
-use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum Tile {
@@ -9,19 +9,17 @@ enum Tile {
 
 #[derive(Clone)]
 pub struct Valley {
-    tiles: Vec<Tile>,
-    height: usize,
-    width: usize,
+    tiles: Grid<Tile>,
 }
 
 impl TryFrom<char> for Tile {
-    type Error = ParseError;
+    type Error = ParseErrorKind;
 
     fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
             '.' => Ok(Tile::Ash),
             '#' => Ok(Tile::Rock),
-            _ => Err(ParseError::UnknownCharacter(c)),
+            _ => Err(ParseErrorKind::UnknownCharacter(c)),
         }
     }
 }
@@ -31,13 +29,12 @@ type VerticalMirror = usize;
 
 impl Valley {
     pub fn smudged_mirror_pos(&self) -> Option<Either<HorizontalMirror, VerticalMirror>> {
-        (0..self.width)
-            .flat_map(move |x| (0..self.height).map(move |y| (x, y)))
+        (0..self.tiles.width())
+            .flat_map(move |x| (0..self.tiles.height()).map(move |y| (x, y)))
             .find_map(|(x, y)| {
                 let mut clone = self.clone();
 
-                let i = x + y * self.width;
-                clone.tiles[i] = match clone.tiles[i] {
+                clone.tiles[(x, y)] = match clone.tiles[(x, y)] {
                     Tile::Ash => Tile::Rock,
                     Tile::Rock => Tile::Ash,
                 };
@@ -70,7 +67,7 @@ impl Valley {
                 let before = self
                     .rows()
                     .rev()
-                    .skip(self.height - y - 1)
+                    .skip(self.tiles.height() - y - 1)
                     .collect::<Vec<_>>();
                 let after = self.rows().skip(*y + 1).collect::<Vec<_>>();
 
@@ -95,7 +92,7 @@ impl Valley {
                 let before = self
                     .cols()
                     .rev()
-                    .skip(self.width - x - 1)
+                    .skip(self.tiles.width() - x - 1)
                     .collect::<Vec<_>>();
                 let after = self.cols().skip(*x + 1).collect::<Vec<_>>();
                 !after.is_empty() && before.starts_with(&after) || after.starts_with(&before)
@@ -109,23 +106,12 @@ impl Valley {
         None
     }
 
-    fn rows(&self) -> impl DoubleEndedIterator<Item = Vec<Tile>> {
-        self.tiles
-            .chunks(self.width)
-            .map(|c| c.to_vec())
-            .collect::<Vec<_>>()
-            .into_iter()
+    fn rows(&self) -> impl DoubleEndedIterator<Item = &[Tile]> {
+        self.tiles.rows()
     }
 
-    fn cols(&self) -> impl DoubleEndedIterator<Item = Vec<Tile>> {
-        (0..self.width)
-            .map(move |x| {
-                (0..self.height)
-                    .map(move |y| self.tiles[x + y * self.width])
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>()
-            .into_iter()
+    fn cols(&self) -> impl DoubleEndedIterator<Item = Vec<Tile>> + '_ {
+        self.tiles.columns().map(|c| c.copied().collect())
     }
 }
 
@@ -133,51 +119,11 @@ impl TryFrom<&[&str]> for Valley {
     type Error = ParseError;
 
     fn try_from(lines: &[&str]) -> Result<Self, Self::Error> {
-        if lines.is_empty() {
-            return Err(ParseError::EmptyInput);
-        }
-
-        let tiles: Vec<Vec<Tile>> = lines
-            .iter()
-            .map(|l| {
-                l.chars()
-                    .map(|c| c.try_into())
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let n_rows = tiles.len();
-        let n_cols = tiles[0].len();
-
-        if tiles.iter().any(|v| v.len() != n_cols) {
-            return Err(ParseError::UnevenGrid);
-        }
-
         Ok(Self {
-            tiles: tiles.into_iter().flatten().collect(),
-            height: n_rows,
-            width: n_cols,
+            tiles: Grid::parse_lines(lines, Tile::try_from)?,
         })
     }
 }
-
-#[derive(Debug)]
-pub enum ParseError {
-    EmptyInput,
-    UnknownCharacter(char),
-    UnevenGrid,
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::UnknownCharacter(c) => write!(f, "Unknown character: {c}"),
-            ParseError::EmptyInput => write!(f, "Expected lines"),
-            ParseError::UnevenGrid => write!(f, "Expected even grid"),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
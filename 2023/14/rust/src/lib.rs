@@ -0,0 +1,30 @@
+pub mod tilt;
+
+use aoc_common::{Error, Solution};
+use std::io::Read;
+use tilt::Platform;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 14;
+
+    type Input = Platform;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        Platform::from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        let mut platform = input.clone();
+        platform.tilt_north();
+        Ok(platform.total_weight())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input.cycled_many(1_000_000_000).total_weight())
+    }
+}
@@ -1,7 +1,7 @@
+use aoc_common::{Error, Grid, ParseErrorKind};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum Tile {
@@ -12,55 +12,26 @@ enum Tile {
 
 #[derive(Clone)]
 pub struct Platform {
-    tiles: Vec<Vec<Tile>>,
-    width: usize,
-    height: usize,
+    tiles: Grid<Tile>,
 }
 
 impl Platform {
-    pub fn from_stream(r: impl Read) -> Result<Self, ParseError> {
-        let reader = BufReader::new(r);
-
-        let tiles: Vec<Vec<Tile>> = reader
-            .lines()
-            .map(|l| {
-                let line = l.map_err(ParseError::IO)?;
-                line.chars()
-                    .map(|c| c.try_into())
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        if tiles.is_empty() {
-            return Err(ParseError::EmptyInput);
-        }
-
-        let height = tiles.len();
-        let width = tiles[0].len();
-
-        if tiles.iter().any(|v| v.len() != width) {
-            return Err(ParseError::UnevenGrid);
-        }
-
+    pub fn from_stream(r: impl Read) -> Result<Self, Error> {
         Ok(Self {
-            tiles,
-            width,
-            height,
+            tiles: Grid::parse(r, Tile::try_from)?,
         })
     }
 
     pub fn tilt_north(&mut self) {
-        for y in 1..self.height {
-            for x in 0..self.width {
-                let curr = &self.tiles[y][x];
-
-                if *curr != Tile::Round {
+        for y in 1..self.tiles.height() {
+            for x in 0..self.tiles.width() {
+                if self.tiles[(x, y)] != Tile::Round {
                     continue;
                 }
 
                 let mut dist = 0;
                 for y_check in (0..y).rev() {
-                    if self.tiles[y_check][x] == Tile::Empty {
+                    if self.tiles[(x, y_check)] == Tile::Empty {
                         dist += 1;
                     } else {
                         break;
@@ -68,27 +39,15 @@ impl Platform {
                 }
 
                 if dist > 0 {
-                    self.tiles[y - dist][x] = Tile::Round;
-                    self.tiles[y][x] = Tile::Empty;
+                    self.tiles.swap((x, y - dist), (x, y));
                 }
             }
         }
     }
 
-    #[allow(clippy::needless_range_loop)]
     pub fn rotated_cw_90(&self) -> Self {
-        let mut tiles = vec![vec![Tile::Empty; self.height]; self.width];
-
-        for x in 0..self.width {
-            for y in 0..self.height {
-                tiles[y][x] = self.tiles[self.width - x - 1][y];
-            }
-        }
-
         Self {
-            tiles,
-            width: self.height,
-            height: self.width,
+            tiles: self.tiles.rotated_cw(),
         }
     }
 
@@ -139,31 +98,15 @@ impl Platform {
     pub fn total_weight(&self) -> usize {
         self.tiles
             .iter()
-            .enumerate()
-            .map(|(y, row)| {
-                row.iter()
-                    .filter(|t| *t == &Tile::Round)
-                    .map(|_| self.height - y)
-                    .sum::<usize>()
-            })
+            .filter(|(_, t)| **t == Tile::Round)
+            .map(|((_, y), _)| self.tiles.height() - y)
             .sum()
     }
 }
 
 impl From<&Platform> for String {
     fn from(val: &Platform) -> Self {
-        val.tiles
-            .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|t| {
-                        let c: char = (*t).into();
-                        c
-                    })
-                    .collect::<String>()
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
+        val.tiles.to_string()
     }
 }
 
@@ -173,6 +116,12 @@ impl Display for Platform {
     }
 }
 
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
 impl From<Tile> for char {
     fn from(value: Tile) -> Self {
         match value {
@@ -184,33 +133,14 @@ impl From<Tile> for char {
 }
 
 impl TryFrom<char> for Tile {
-    type Error = ParseError;
+    type Error = ParseErrorKind;
 
     fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
             'O' => Ok(Tile::Round),
             '#' => Ok(Tile::Cube),
             '.' => Ok(Tile::Empty),
-            _ => Err(ParseError::UnknownCharacter(c)),
-        }
-    }
-}
-
-#[derive(Debug)]
-pub enum ParseError {
-    IO(io::Error),
-    EmptyInput,
-    UnknownCharacter(char),
-    UnevenGrid,
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::UnknownCharacter(c) => write!(f, "Unknown character: {c}"),
-            ParseError::EmptyInput => write!(f, "Expected lines"),
-            ParseError::UnevenGrid => write!(f, "Expected even grid"),
-            ParseError::IO(e) => write!(f, "Could not read file: {e}"),
+            _ => Err(ParseErrorKind::UnknownCharacter(c)),
         }
     }
 }
@@ -385,7 +315,7 @@ O.#..O.#.#
 
         let r = StringReader::new(input);
 
-        let mut platform = Platform::from_stream(r).unwrap();
+        let platform = Platform::from_stream(r).unwrap();
 
         let cycled = platform.cycled_many(1_000_000_000);
 
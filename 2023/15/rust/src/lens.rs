@@ -1,7 +1,7 @@
+use aoc_common::{ParseError, ParseErrorKind};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub struct Manual {
@@ -109,55 +109,58 @@ impl FromStr for Manual {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            steps: Self::parse_line(s)
-                .into_iter()
-                .map(|s| s.parse::<Step>())
-                .collect::<Result<Vec<_>, _>>()?,
-        })
+        let mut offset = 0;
+        let mut steps = vec![];
+
+        for (raw, step) in s.split(',').zip(Self::parse_line(s)) {
+            let start = offset + (raw.len() - raw.trim_start().len());
+            steps.push(step.parse::<Step>().map_err(|k| locate(k, s, start))?);
+            offset += raw.len() + 1;
+        }
+
+        Ok(Self { steps })
     }
 }
 
+/// Points the error at the byte `offset` of the whole input, which may span several lines.
+fn locate(kind: ParseErrorKind, input: &str, offset: usize) -> ParseError {
+    let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = input[line_start..].lines().next().unwrap_or_default();
+
+    ParseError::new(kind).at(
+        input[..line_start].matches('\n').count(),
+        input[line_start..offset].chars().count(),
+        line,
+    )
+}
+
 lazy_static! {
     static ref STEP_REGEX: Regex = Regex::new(r"^([a-z]+)(=|-)(\d+)?$").unwrap();
 }
 
 impl FromStr for Step {
-    type Err = ParseError;
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cap = STEP_REGEX
-            .captures(s)
-            .ok_or(ParseError::UnknownOperation(s.to_owned()))?;
+        let unknown = || ParseErrorKind::Invalid(format!("Unknown operation specification: {s}"));
+        let cap = STEP_REGEX.captures(s).ok_or_else(unknown)?;
 
         let step = match cap[2].to_owned().as_str() {
             "=" => Step::Add(Lens {
                 label: cap[1].to_owned(),
-                focal: cap[3]
-                    .parse::<u32>()
-                    .map_err(|_| ParseError::UnknownOperation(s.to_owned()))?,
+                focal: cap
+                    .get(3)
+                    .and_then(|m| m.as_str().parse::<u32>().ok())
+                    .ok_or_else(unknown)?,
             }),
             "-" => Step::Remove(cap[1].to_owned()),
-            _ => return Err(ParseError::UnknownOperation(s.to_owned()))
+            _ => return Err(unknown()),
         };
 
         Ok(step)
     }
 }
 
-#[derive(Debug)]
-pub enum ParseError {
-    UnknownOperation(String),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::UnknownOperation(s) => write!(f, "Unknown operation specification: {s}"),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,4 +231,20 @@ mod tests {
 
         assert_eq!(145, config.focusing_power())
     }
+
+    #[test]
+    fn unknown_operation_location() {
+        let e = "rn=1,cm-,\n qp+3,cm=2".parse::<Manual>().err().unwrap();
+
+        assert_eq!(
+            ParseErrorKind::Invalid("Unknown operation specification: qp+3".to_owned()),
+            e.kind
+        );
+        assert_eq!(
+            Some((2, 2, " qp+3,cm=2")),
+            e.location
+                .as_ref()
+                .map(|l| (l.line, l.column, l.snippet.as_str()))
+        );
+    }
 }
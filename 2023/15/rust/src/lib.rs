@@ -0,0 +1,33 @@
+pub mod lens;
+
+use aoc_common::{Error, Solution};
+use lens::Manual;
+use std::io::Read;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 15;
+
+    type Input = (Vec<String>, Manual);
+    type Part1 = u64;
+    type Part2 = u32;
+
+    fn parse(mut input: impl Read) -> Result<Self::Input, Error> {
+        let mut line = String::new();
+        input.read_to_string(&mut line)?;
+
+        let manual = line.parse::<Manual>()?;
+
+        Ok((Manual::parse_line(&line), manual))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(Manual::hash_sum(&input.0))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input.1.create_configuration().focusing_power())
+    }
+}
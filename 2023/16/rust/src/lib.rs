@@ -0,0 +1,28 @@
+pub mod light;
+
+use aoc_common::{Direction, Error, Position, Solution};
+use light::Room;
+use std::io::Read;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 16;
+
+    type Input = Room;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        Room::from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(input.energized(Position::new(0, 0), Direction::East))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        Ok(input.max_energized())
+    }
+}
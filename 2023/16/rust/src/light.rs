@@ -1,20 +1,18 @@
+use aoc_common::{Direction, Error, Grid, ParseErrorKind, Position};
 use either::{Either, Left, Right};
+use rayon::prelude::*;
 use std::collections::HashSet;
-use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 
 pub struct Room {
-    tiles: Vec<Vec<Tile>>,
-    width: usize,
-    height: usize,
+    tiles: Grid<Tile>,
 }
 
 struct Tile {
     instrument: Option<Box<dyn Instrument>>,
 }
 
-trait Instrument {
+trait Instrument: Send + Sync {
     fn direct_light(&self, dir: Direction) -> Either<Direction, [Direction; 2]>;
 }
 
@@ -28,25 +26,12 @@ enum Splitter {
     Vertical,
 }
 
-#[derive(Copy, Clone, Hash, Eq, PartialEq)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
 impl Instrument for Mirror {
     fn direct_light(&self, dir: Direction) -> Either<Direction, [Direction; 2]> {
         match (self, dir) {
-            (Mirror::NorthWest, Direction::North) => Left(Direction::East),
-            (Mirror::NorthWest, Direction::East) => Left(Direction::North),
-            (Mirror::NorthWest, Direction::South) => Left(Direction::West),
-            (Mirror::NorthWest, Direction::West) => Left(Direction::South),
-            (Mirror::SouthWest, Direction::North) => Left(Direction::West),
-            (Mirror::SouthWest, Direction::East) => Left(Direction::South),
-            (Mirror::SouthWest, Direction::South) => Left(Direction::East),
-            (Mirror::SouthWest, Direction::West) => Left(Direction::North),
+            (Mirror::NorthWest, Direction::North | Direction::South)
+            | (Mirror::SouthWest, Direction::East | Direction::West) => Left(dir.turn_right()),
+            _ => Left(dir.turn_left()),
         }
     }
 }
@@ -67,32 +52,13 @@ impl Instrument for Splitter {
 }
 
 impl Room {
-    pub fn from_stream(r: impl Read) -> Result<Self, ParseError> {
-        let reader = BufReader::new(r);
-
-        let tiles: Vec<Vec<Tile>> = reader
-            .lines()
-            .map(|l| {
-                let line = l.map_err(ParseError::IO)?;
-                line.chars().map(|c| c.try_into()).collect::<Result<_, _>>()
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let height = tiles.len();
-        let width = tiles[0].len();
-
-        if tiles.iter().any(|row| row.len() != width) {
-            return Err(ParseError::UnevenGrid);
-        }
-
+    pub fn from_stream(r: impl Read) -> Result<Self, Error> {
         Ok(Room {
-            tiles,
-            width,
-            height,
+            tiles: Grid::parse(r, Tile::try_from)?,
         })
     }
 
-    pub fn energized(&self, pos: (i32, i32), dir: Direction) -> usize {
+    pub fn energized(&self, pos: Position, dir: Direction) -> usize {
         let mut visited = HashSet::new();
 
         let mut beams = vec![(pos, dir)];
@@ -101,15 +67,14 @@ impl Room {
             let mut curr_pos = beam.0;
             let mut curr_dir = beam.1;
 
-            while visited.get(&(curr_pos, curr_dir)).is_none()
-                && curr_pos.0 >= 0
-                && curr_pos.0 < self.height as i32
-                && curr_pos.1 >= 0
-                && curr_pos.1 < self.width as i32
-            {
+            while !visited.contains(&(curr_pos, curr_dir)) {
+                let Some(curr_tile) = self.tiles.get_signed(curr_pos) else {
+                    break;
+                };
+
                 visited.insert((curr_pos, curr_dir));
 
-                let curr_ins = &self.tiles[curr_pos.0 as usize][curr_pos.1 as usize].instrument;
+                let curr_ins = &curr_tile.instrument;
 
                 curr_dir = match curr_ins {
                     None => curr_dir,
@@ -122,12 +87,7 @@ impl Room {
                     },
                 };
 
-                match curr_dir {
-                    Direction::North => curr_pos.0 -= 1,
-                    Direction::East => curr_pos.1 += 1,
-                    Direction::South => curr_pos.0 += 1,
-                    Direction::West => curr_pos.1 -= 1,
-                }
+                curr_pos = curr_pos.step(curr_dir);
             }
         }
 
@@ -139,55 +99,33 @@ impl Room {
     }
 
     pub fn max_energized(&self) -> usize {
-        let top: Vec<_> = vec![0; self.width]
-            .into_iter()
-            .zip(0..self.width as i32)
-            .collect();
-
-        let left: Vec<_> = (0..self.height as i32).zip(vec![0; self.height]).collect();
-
-        let bottom: Vec<_> = vec![self.height as i32; self.width]
-            .into_iter()
-            .zip(0..self.width as i32)
-            .collect();
-
-        let right: Vec<_> = (0..self.height as i32)
-            .zip(vec![self.width as i32; self.height])
-            .collect();
-
-        let top_illu = top
-            .iter()
-            .map(|pos| self.energized(*pos, Direction::South))
-            .max()
-            .unwrap();
-
-        let left_illu = left
-            .iter()
-            .map(|pos| self.energized(*pos, Direction::East))
-            .max()
-            .unwrap();
-
-        let bottom_illu = bottom
-            .iter()
-            .map(|pos| self.energized(*pos, Direction::North))
+        let (width, height) = (self.tiles.width() as i64, self.tiles.height() as i64);
+
+        let vertical = (0..width).flat_map(|x| {
+            [
+                (Position::new(x, 0), Direction::South),
+                (Position::new(x, height - 1), Direction::North),
+            ]
+        });
+        let horizontal = (0..height).flat_map(|y| {
+            [
+                (Position::new(0, y), Direction::East),
+                (Position::new(width - 1, y), Direction::West),
+            ]
+        });
+
+        vertical
+            .chain(horizontal)
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(pos, dir)| self.energized(pos, dir))
             .max()
-            .unwrap();
-
-        let right_illu = right
-            .iter()
-            .map(|pos| self.energized(*pos, Direction::West))
-            .max()
-            .unwrap();
-
-        *[top_illu, left_illu, bottom_illu, right_illu]
-            .iter()
-            .max()
-            .unwrap()
+            .unwrap_or(0)
     }
 }
 
 impl TryFrom<char> for Tile {
-    type Error = ParseError;
+    type Error = ParseErrorKind;
 
     fn try_from(sym: char) -> Result<Self, Self::Error> {
         let instrument: Option<Box<dyn Instrument>> = match sym {
@@ -196,30 +134,13 @@ impl TryFrom<char> for Tile {
             '-' => Some(Box::new(Splitter::Horizontal)),
             '/' => Some(Box::new(Mirror::NorthWest)),
             '\\' => Some(Box::new(Mirror::SouthWest)),
-            _ => return Err(ParseError::UnknownCharacter(sym)),
+            _ => return Err(ParseErrorKind::UnknownCharacter(sym)),
         };
 
         Ok(Self { instrument })
     }
 }
 
-#[derive(Debug)]
-pub enum ParseError {
-    IO(io::Error),
-    UnknownCharacter(char),
-    UnevenGrid,
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::IO(e) => write!(f, "Could not read file: {e}"),
-            ParseError::UnknownCharacter(c) => write!(f, "Unknown character: {c}"),
-            ParseError::UnevenGrid => write!(f, "Expected even grid"),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,7 +162,7 @@ mod tests {
         let reader = StringReader::new(input);
         let room = Room::from_stream(reader).unwrap();
 
-        let energized = room.energized((0, 0), Direction::East);
+        let energized = room.energized(Position::new(0, 0), Direction::East);
 
         assert_eq!(46, energized);
     }
@@ -266,4 +187,15 @@ mod tests {
 
         assert_eq!(51, energized);
     }
+
+    #[test]
+    fn max_energized_from_bottom() {
+        let input = "-..
+...
+...";
+
+        let room = Room::from_stream(StringReader::new(input)).unwrap();
+
+        assert_eq!(5, room.max_energized());
+    }
 }
@@ -0,0 +1,63 @@
+pub mod path;
+
+use aoc_common::{Error, Position, Solution};
+use path::{City, Crucible};
+use std::io::Read;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 17;
+
+    type Input = City;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        City::from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        min_heat_loss(input, Crucible::Small)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        min_heat_loss(input, Crucible::Ultra)
+    }
+}
+
+fn min_heat_loss(city: &City, crucible: Crucible) -> Result<u32, Error> {
+    let path = city.navigate(
+        Position { x: 0, y: 0 },
+        Position {
+            x: city.width() - 1,
+            y: city.height() - 1,
+        },
+        crucible,
+    );
+
+    path.map(|p| p.iter().map(|b| b.heat_loss).sum())
+        .ok_or(Error::Solve("No path reaches the goal".to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_is_goal() {
+        let city = Day17::parse("1".as_bytes()).unwrap();
+
+        assert_eq!(0, Day17::part1(&city).unwrap());
+        assert_eq!(0, Day17::part2(&city).unwrap());
+    }
+
+    #[test]
+    fn ultra_cannot_stop() {
+        let city = Day17::parse("11\n11".as_bytes()).unwrap();
+
+        assert_eq!(2, Day17::part1(&city).unwrap());
+        assert!(matches!(Day17::part2(&city), Err(Error::Solve(_))));
+    }
+}
@@ -1,14 +1,11 @@
-use crate::path::Direction::{East, North, South, West};
+use aoc_common::{Direction, Error, Grid, ParseErrorKind, Position};
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::fmt::{Debug, Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
+use tracing::debug;
 
 pub struct City {
-    blocks: HashMap<Position, Block>,
-    pub width: i32,
-    pub height: i32,
+    blocks: Grid<Block>,
 }
 
 pub struct Block {
@@ -16,12 +13,6 @@ pub struct Block {
     pub pos: Position,
 }
 
-#[derive(Copy, Clone, Hash, Eq, PartialEq)]
-pub struct Position {
-    pub x: i32,
-    pub y: i32,
-}
-
 #[derive(Clone, Copy, Hash, Eq, PartialEq)]
 struct State {
     pos: Position,
@@ -29,57 +20,16 @@ struct State {
     steps: u32,
 }
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
 #[derive(PartialEq)]
 pub enum Crucible {
     Small,
     Ultra,
 }
 
-impl Debug for Position {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({},{})", self.x, self.y)
-    }
-}
-
-impl Position {
-    fn mv(&self, dir: Direction) -> Position {
-        match dir {
-            North => Position {
-                x: self.x,
-                y: self.y - 1,
-            },
-            East => Position {
-                x: self.x + 1,
-                y: self.y,
-            },
-            South => Position {
-                x: self.x,
-                y: self.y + 1,
-            },
-            West => Position {
-                x: self.x - 1,
-                y: self.y,
-            },
-        }
-    }
-
-    fn l1_distance(&self, p2: &Position) -> u32 {
-        self.x.abs_diff(p2.x) + self.y.abs_diff(p2.y)
-    }
-}
-
 impl State {
     fn mv(&self, dir: Direction) -> State {
         State {
-            pos: self.pos.mv(dir),
+            pos: self.pos.step(dir),
             dir,
             steps: if self.dir == dir { self.steps + 1 } else { 1 },
         }
@@ -87,63 +37,48 @@ impl State {
 }
 
 impl<'a> City {
-    pub fn from_stream(r: impl Read) -> Result<Self, ParseError> {
-        let reader = BufReader::new(r);
-
-        let binding: Vec<Vec<_>> = reader
-            .lines()
-            .enumerate()
-            .map(|(y, l)| {
-                let line = l.map_err(ParseError::IO)?;
-                let blocks = line
-                    .chars()
-                    .enumerate()
-                    .map(|(x, c)| {
-                        let heat_loss = c.to_digit(10).ok_or(ParseError::UnknownCharacter(c))?;
-                        let pos = Position {
-                            x: x as i32,
-                            y: y as i32,
-                        };
-
-                        Ok((pos, Block { pos, heat_loss }))
-                    })
-                    .collect::<Result<_, _>>()?;
-                Ok(blocks)
-            })
-            .collect::<Result<_, _>>()?;
+    pub fn from_stream(r: impl Read) -> Result<Self, Error> {
+        let heat_losses = Grid::parse(r, |c| {
+            c.to_digit(10).ok_or(ParseErrorKind::UnknownCharacter(c))
+        })?;
 
-        let blocks: HashMap<Position, Block> = binding.into_iter().flatten().collect();
+        let blocks = heat_losses.map(|pos, &heat_loss| Block {
+            heat_loss,
+            pos: pos.into(),
+        });
 
-        if blocks.is_empty() {
-            return Err(ParseError::EmptyCity);
-        }
+        Ok(City { blocks })
+    }
 
-        let width = blocks.iter().map(|b| b.0.x).max().unwrap() + 1;
-        let height = blocks.iter().map(|b| b.0.y).max().unwrap() + 1;
+    pub fn width(&self) -> i64 {
+        self.blocks.width() as i64
+    }
 
-        if (0..width).any(|x| (0..height).any(|y| blocks.get(&Position { x, y }).is_none())) {
-            return Err(ParseError::UnevenGrid);
-        }
+    pub fn height(&self) -> i64 {
+        self.blocks.height() as i64
+    }
 
-        Ok(City {
-            blocks,
-            width,
-            height,
-        })
+    fn block(&self, pos: &Position) -> &Block {
+        &self.blocks[*pos]
     }
 
     // A* search algorithm (https://en.wikipedia.org/wiki/A*_search_algorithm)
+    // The path leaves out the start block, and is `None` if the crucible cannot stop at the goal.
     pub fn navigate(
         &'a self,
         start: Position,
         goal: Position,
         crucible: Crucible,
-    ) -> Vec<&'a Block> {
+    ) -> Option<Vec<&'a Block>> {
+        if start == goal {
+            return Some(vec![]);
+        }
+
         let valid_start_moves = self.valid_moves(
             &State {
                 pos: start,
                 steps: 0,
-                dir: South, // unimportant
+                dir: Direction::South, // unimportant
             },
             &crucible,
         );
@@ -153,7 +88,7 @@ impl<'a> City {
             .map(|s| {
                 Reverse(Candidate {
                     state: *s,
-                    distance: s.pos.l1_distance(&goal),
+                    distance: s.pos.manhattan(&goal),
                 })
             })
             .collect();
@@ -164,7 +99,7 @@ impl<'a> City {
 
         let mut losses: HashMap<State, u32> = valid_start_moves
             .iter()
-            .map(|s| (*s, self.blocks.get(&s.pos).unwrap().heat_loss))
+            .map(|s| (*s, self.block(&s.pos).heat_loss))
             .collect();
 
         let mut best_state: Option<State> = None;
@@ -182,11 +117,11 @@ impl<'a> City {
                 best_state = Some(curr.state);
                 best_loss = curr_loss;
 
-                println!("Best loss {best_loss}, remaining set: {}", open_set.len())
+                debug!(best_loss, remaining = open_set.len(), "reached the goal");
             }
 
             for neighbor in self.valid_moves(&curr.state, &crucible) {
-                let neighbor_loss = curr_loss + self.blocks.get(&neighbor.pos).unwrap().heat_loss;
+                let neighbor_loss = curr_loss + self.block(&neighbor.pos).heat_loss;
                 let prev_neighbor_loss = *losses.get(&neighbor).unwrap_or(&u32::MAX);
 
                 if neighbor_loss < best_loss && neighbor_loss < prev_neighbor_loss {
@@ -196,7 +131,7 @@ impl<'a> City {
                     if !queued.contains(&neighbor) {
                         open_set.push(Reverse(Candidate {
                             state: neighbor,
-                            distance: neighbor.pos.l1_distance(&goal),
+                            distance: neighbor.pos.manhattan(&goal),
                         }));
 
                         queued.insert(neighbor);
@@ -205,7 +140,7 @@ impl<'a> City {
             }
         }
 
-        self.reconstruct_path(&came_from, &best_state.unwrap())
+        best_state.map(|s| self.reconstruct_path(&came_from, &s))
     }
 
     fn reconstruct_path(
@@ -213,13 +148,13 @@ impl<'a> City {
         came_from: &HashMap<State, State>,
         last: &State,
     ) -> Vec<&'a Block> {
-        let mut path = vec![self.blocks.get(&last.pos).unwrap()];
+        let mut path = vec![self.block(&last.pos)];
 
         let mut curr = last;
 
         while came_from.contains_key(curr) {
             curr = came_from.get(curr).unwrap();
-            path.push(self.blocks.get(&curr.pos).unwrap());
+            path.push(self.block(&curr.pos));
         }
 
         path.into_iter().rev().collect()
@@ -228,12 +163,7 @@ impl<'a> City {
     fn valid_moves(&self, curr: &State, crucible: &Crucible) -> Vec<State> {
         let mut next: Vec<State> = vec![];
 
-        let candidates = match curr.dir {
-            North => [North, East, West],
-            East => [North, East, South],
-            South => [East, South, West],
-            West => [North, South, West],
-        };
+        let candidates = [curr.dir.turn_left(), curr.dir, curr.dir.turn_right()];
 
         for dir in candidates {
             if self.can_move(curr, dir, crucible) {
@@ -245,12 +175,10 @@ impl<'a> City {
     }
 
     fn can_move(&self, curr: &State, dir: Direction, crucible: &Crucible) -> bool {
-        let in_bounds = match dir {
-            North => curr.pos.y > 0,
-            East => curr.pos.x < self.width - 1,
-            South => curr.pos.y < self.height - 1,
-            West => curr.pos.x > 0,
-        };
+        let in_bounds = curr
+            .pos
+            .checked_step(dir, self.blocks.width(), self.blocks.height())
+            .is_some();
 
         let steps_ok = match crucible {
             Crucible::Small => curr.dir != dir || curr.steps < 3,
@@ -267,7 +195,7 @@ impl<'a> City {
 
 struct Candidate {
     state: State,
-    distance: u32,
+    distance: u64,
 }
 
 impl Eq for Candidate {}
@@ -290,25 +218,6 @@ impl Ord for Candidate {
     }
 }
 
-#[derive(Debug)]
-pub enum ParseError {
-    IO(io::Error),
-    UnknownCharacter(char),
-    EmptyCity,
-    UnevenGrid,
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::IO(e) => write!(f, "Could not read file: {e}"),
-            ParseError::UnknownCharacter(c) => write!(f, "Unknown character: {c}"),
-            ParseError::UnevenGrid => write!(f, "Expected even grid"),
-            ParseError::EmptyCity => write!(f, "Expected city blocks"),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,14 +242,16 @@ mod tests {
         let reader = StringReader::new(input);
         let city = City::from_stream(reader).unwrap();
 
-        let path = city.navigate(
-            Position { x: 0, y: 0 },
-            Position {
-                x: city.width - 1,
-                y: city.height - 1,
-            },
-            Crucible::Small,
-        );
+        let path = city
+            .navigate(
+                Position { x: 0, y: 0 },
+                Position {
+                    x: city.width() - 1,
+                    y: city.height() - 1,
+                },
+                Crucible::Small,
+            )
+            .unwrap();
 
         let total_loss: u32 = path.iter().map(|b| b.heat_loss).sum();
 
@@ -366,14 +277,16 @@ mod tests {
         let reader = StringReader::new(input);
         let city = City::from_stream(reader).unwrap();
 
-        let path = city.navigate(
-            Position { x: 0, y: 0 },
-            Position {
-                x: city.width - 1,
-                y: city.height - 1,
-            },
-            Crucible::Ultra,
-        );
+        let path = city
+            .navigate(
+                Position { x: 0, y: 0 },
+                Position {
+                    x: city.width() - 1,
+                    y: city.height() - 1,
+                },
+                Crucible::Ultra,
+            )
+            .unwrap();
 
         let total_loss: u32 = path.iter().map(|b| b.heat_loss).sum();
 
@@ -391,14 +304,16 @@ mod tests {
         let reader = StringReader::new(input);
         let city = City::from_stream(reader).unwrap();
 
-        let path = city.navigate(
-            Position { x: 0, y: 0 },
-            Position {
-                x: city.width - 1,
-                y: city.height - 1,
-            },
-            Crucible::Ultra,
-        );
+        let path = city
+            .navigate(
+                Position { x: 0, y: 0 },
+                Position {
+                    x: city.width() - 1,
+                    y: city.height() - 1,
+                },
+                Crucible::Ultra,
+            )
+            .unwrap();
 
         let total_loss: u32 = path.iter().map(|b| b.heat_loss).sum();
 
@@ -1,37 +1,31 @@
-use crate::dig::Direction::{Down, Left, Right, Up};
 use crate::dig::Terrain::{Ground, Trench};
+use aoc_common::Direction::{self, East as Right, North as Up, South as Down, West as Left};
+use aoc_common::{read_lines, Error, Grid, ParseError, ParseErrorKind, Position};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 use std::str::FromStr;
 
+#[derive(Clone)]
 pub struct Plan {
     instructions: Vec<Instruction>,
     handedness: Handedness,
 }
 
+#[derive(Clone)]
 enum Handedness {
     Right,
     Left,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 struct Instruction {
     dir: Direction,
     count: i64,
     color: String,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Clone, PartialEq)]
 enum Terrain {
     Trench,
@@ -39,9 +33,7 @@ enum Terrain {
 }
 
 pub struct LavaPool {
-    terrain: Vec<Vec<Terrain>>,
-    width: usize,
-    height: usize,
+    terrain: Grid<Terrain>,
 }
 
 pub struct Elves {}
@@ -54,13 +46,13 @@ impl Elves {
             .windows(2)
             .zip(plan.instructions.iter().map(|i| i.dir))
             .flat_map(|(w, dir)| {
-                let dug = (w[0].0.min(w[1].0)..=w[0].0.max(w[1].0)).flat_map(|x| {
-                    (w[0].1.min(w[1].1)..=w[0].1.max(w[1].1)).map(move |y| (x, y, dir))
+                let dug = (w[0].x.min(w[1].x)..=w[0].x.max(w[1].x)).flat_map(|x| {
+                    (w[0].y.min(w[1].y)..=w[0].y.max(w[1].y)).map(move |y| (x, y, dir))
                 });
 
                 let last = dug.clone().last().unwrap();
 
-                if last.0 == w[0].0 && last.1 == w[0].1 {
+                if last.0 == w[0].x && last.1 == w[0].y {
                     dug.rev().collect::<Vec<_>>()
                 } else {
                     dug.collect::<Vec<_>>()
@@ -80,24 +72,17 @@ impl Elves {
         let width = (x_max - x_min + 1) as usize;
         let height = (y_max - y_min + 1) as usize;
 
-        let mut terrain: Vec<Vec<Terrain>> = vec![vec![Ground; width]; height];
+        let mut terrain = Grid::new(width, height, Ground);
 
         for p in path {
-            terrain[(p.1 - y_min) as usize][(p.0 - x_min) as usize] = Trench;
+            terrain[((p.0 - x_min) as usize, (p.1 - y_min) as usize)] = Trench;
         }
 
         let mut inside_seed = Self::choose_inside_seed(plan);
         inside_seed.0 -= x_min;
         inside_seed.1 -= y_min;
 
-        (
-            LavaPool {
-                terrain,
-                width,
-                height,
-            },
-            inside_seed,
-        )
+        (LavaPool { terrain }, inside_seed)
     }
 
     pub fn choose_inside_seed(plan: &Plan) -> (i64, i64) {
@@ -118,19 +103,12 @@ impl Elves {
         let mut stack = vec![seed];
 
         while let Some(curr) = stack.pop() {
-            if curr.0 < 0
-                || curr.0 >= pool.width as i64
-                || curr.1 < 0
-                || curr.1 >= pool.height as i64
-            {
-                continue;
-            }
-
-            if pool.terrain[curr.1 as usize][curr.0 as usize] == Trench {
-                continue;
+            match pool.terrain.get_signed(curr) {
+                Some(Ground) => {}
+                _ => continue,
             }
 
-            pool.terrain[curr.1 as usize][curr.0 as usize] = Trench;
+            pool.terrain[(curr.0 as usize, curr.1 as usize)] = Trench;
 
             stack.push((curr.0 - 1, curr.1));
             stack.push((curr.0 + 1, curr.1));
@@ -167,18 +145,12 @@ impl Elves {
         }
     }
 
-    fn plan_points(plan: &Plan) -> Vec<(i64, i64)> {
+    fn plan_points(plan: &Plan) -> Vec<Position> {
         plan.instructions
             .iter()
-            .fold(vec![(0, 0)], |mut acc, curr| {
+            .fold(vec![Position::default()], |mut acc, curr| {
                 let pos = acc.last().unwrap();
-                let moved = match curr.dir {
-                    Up => (pos.0, pos.1 - curr.count),
-                    Down => (pos.0, pos.1 + curr.count),
-                    Left => (pos.0 - curr.count, pos.1),
-                    Right => (pos.0 + curr.count, pos.1),
-                };
-                acc.push(moved);
+                acc.push(pos.step_by(curr.dir, curr.count));
                 acc
             })
     }
@@ -189,13 +161,14 @@ impl Elves {
 
         let edges = points
             .windows(2)
-            .map(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1))
+            .map(|w| w[0].manhattan(&w[1]))
             .sum::<u64>();
 
         let area = points.windows(2).fold(0, |mut acc, w| {
-            acc += w[0].0 * w[1].1 - w[1].0 * w[0].1;
+            acc += w[0].x * w[1].y - w[1].x * w[0].y;
             acc
-        }) as u64 / 2;
+        }) as u64
+            / 2;
 
         area + edges / 2 + 1
     }
@@ -203,85 +176,61 @@ impl Elves {
 
 impl LavaPool {
     pub fn volume(&self) -> usize {
-        self.terrain
-            .iter()
-            .flatten()
-            .filter(|t| **t == Trench)
-            .count()
+        self.terrain.iter().filter(|(_, t)| **t == Trench).count()
     }
 }
 
 impl Display for LavaPool {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.terrain
-                .iter()
-                .map(|row| row.iter().map(char::from).collect::<String>())
-                .collect::<Vec<_>>()
-                .join("\n")
-        )
+        write!(f, "{}", self.terrain)
     }
 }
 
 impl FromStr for LavaPool {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let terrain = s
-            .split('\n')
-            .map(|l| {
-                l.chars()
-                    .map(Terrain::try_from)
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let width = s.split('\n').next().unwrap().len();
-        let height = s.split('\n').count();
+        let lines: Vec<_> = s.split('\n').collect();
 
         Ok(LavaPool {
-            terrain,
-            width,
-            height,
+            terrain: Grid::parse_lines(&lines, Terrain::try_from)?,
         })
     }
 }
 
-impl From<&Terrain> for char {
-    fn from(t: &Terrain) -> Self {
-        match t {
-            Trench => '#',
-            Ground => '.',
+impl Display for Terrain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Trench => write!(f, "#"),
+            Ground => write!(f, "."),
         }
     }
 }
 
 impl TryFrom<char> for Terrain {
-    type Error = ();
+    type Error = ParseErrorKind;
 
     fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
             '#' => Ok(Trench),
             '.' => Ok(Ground),
-            _ => Err(()),
+            _ => Err(ParseErrorKind::UnknownCharacter(c)),
         }
     }
 }
 
 impl Plan {
-    pub fn from_stream(r: impl Read) -> Result<Self, ParseError> {
-        let reader = BufReader::new(r);
-
-        let instructions: Vec<_> = reader
-            .lines()
-            .map(|l| {
-                let line = l.map_err(ParseError::IO)?;
-                let instruction = line.parse::<Instruction>()?;
+    pub fn from_stream(r: impl Read) -> Result<Self, Error> {
+        let instructions: Vec<_> = read_lines(r)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let instruction = line
+                    .parse::<Instruction>()
+                    .map_err(|k| ParseError::new(k).at_line(i, &line))?;
                 Ok(instruction)
             })
-            .collect::<Result<_, _>>()?;
+            .collect::<Result<_, Error>>()?;
 
         let handedness = Self::calculate_handedness(&instructions);
 
@@ -294,15 +243,9 @@ impl Plan {
     fn calculate_handedness(instructions: &[Instruction]) -> Handedness {
         let sum: i64 = instructions
             .windows(2)
-            .map(|w| match (&w[0].dir, &w[1].dir) {
-                (Up, Right) => 1,
-                (Right, Down) => 1,
-                (Down, Left) => 1,
-                (Left, Up) => 1,
-                (Right, Up) => -1,
-                (Down, Right) => -1,
-                (Left, Down) => -1,
-                (Up, Left) => -1,
+            .map(|w| match w[1].dir {
+                d if d == w[0].dir.turn_right() => 1,
+                d if d == w[0].dir.turn_left() => -1,
                 _ => 0,
             })
             .sum();
@@ -321,50 +264,23 @@ lazy_static! {
 }
 
 impl FromStr for Instruction {
-    type Err = ParseError;
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cap = INSTRUCTION_REGEX
             .captures(s)
-            .ok_or(ParseError::InvalidInstruction(s.to_owned()))?;
+            .ok_or(ParseErrorKind::Invalid("Invalid instruction".to_owned()))?;
 
-        let dir = Direction::try_from(cap[1].chars().next().unwrap()).unwrap();
-        let count = cap[2].parse::<i64>().unwrap();
+        let dir = Direction::try_from(cap[1].chars().next().unwrap())?;
+        let count = cap[2]
+            .parse::<i64>()
+            .map_err(|_| ParseErrorKind::Invalid(format!("Invalid count: {}", &cap[2])))?;
         let color = cap[3].to_owned();
 
         Ok(Instruction { dir, count, color })
     }
 }
 
-impl TryFrom<char> for Direction {
-    type Error = ();
-
-    fn try_from(c: char) -> Result<Self, Self::Error> {
-        match c {
-            'U' => Ok(Up),
-            'D' => Ok(Down),
-            'L' => Ok(Left),
-            'R' => Ok(Right),
-            _ => Err(()),
-        }
-    }
-}
-
-#[derive(Debug)]
-pub enum ParseError {
-    IO(io::Error),
-    InvalidInstruction(String),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::IO(e) => write!(f, "Could not read file: {e}"),
-            ParseError::InvalidInstruction(s) => write!(f, "Invalid instruction: {s}"),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
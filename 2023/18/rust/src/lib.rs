@@ -0,0 +1,32 @@
+pub mod dig;
+
+use aoc_common::{Error, Solution};
+use dig::{Elves, Plan};
+use std::io::Read;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 18;
+
+    type Input = Plan;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        Plan::from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        let (mut pool, seed) = Elves::dig_sides(input);
+        Elves::dig_out_interior(&mut pool, seed);
+        Ok(pool.volume())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        let mut plan = input.clone();
+        Elves::decode_plan(&mut plan).map_err(Error::invalid)?;
+        Ok(Elves::calculate_volume(&plan))
+    }
+}
@@ -0,0 +1,42 @@
+pub mod workflow;
+
+use aoc_common::{Error, Solution};
+use std::io::Read;
+use workflow::{Elves, ParameterRange};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 19;
+
+    type Input = Elves;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        Elves::from_instructions(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        Ok(input
+            .check_parts()
+            .map_err(|e| Error::Solve(e.to_string()))?
+            .iter()
+            .map(|p| p.rating())
+            .sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        let r = ParameterRange {
+            ranges: "xmas".chars().map(|c| (c, 1..4001)).collect(),
+        };
+
+        Ok(input
+            .find_valid_ranges(r)
+            .map_err(|e| Error::Solve(e.to_string()))?
+            .iter()
+            .map(|r| r.combinations())
+            .sum())
+    }
+}
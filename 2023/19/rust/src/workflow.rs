@@ -1,12 +1,12 @@
 use crate::workflow::Decision::{Accept, Forward, Reject};
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::cmp::Ordering;
 use std::cmp::Ordering::{Greater, Less};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 use std::ops::Range;
 use std::str::FromStr;
 
@@ -55,14 +55,13 @@ impl Part {
 }
 
 impl Elves {
-    pub fn from_instructions(r: impl Read) -> Result<Self, ParseError> {
-        let reader = BufReader::new(r);
+    pub fn from_instructions(r: impl Read) -> Result<Self, Error> {
         let mut reading_workflows = true;
 
         let mut manual = Elves::default();
 
-        for l in reader.lines() {
-            let line = l.map_err(ParseError::IO)?;
+        for (i, line) in read_lines(r)?.into_iter().enumerate() {
+            let located = |k| ParseError::new(k).at_line(i, &line);
 
             if line.is_empty() {
                 reading_workflows = false;
@@ -70,10 +69,10 @@ impl Elves {
             }
 
             if reading_workflows {
-                let w = line.parse::<Workflow>()?;
+                let w = line.parse::<Workflow>().map_err(located)?;
                 manual.workflows.insert(w.name.clone(), w);
             } else {
-                let p = line.parse::<Part>()?;
+                let p = line.parse::<Part>().map_err(located)?;
                 manual.parts.push(p);
             }
         }
@@ -158,7 +157,7 @@ impl Elves {
 
                 match (&c.decision, &inside) {
                     (Accept, Some(r)) => valid_params.push(r.clone()),
-                    (Forward(n), Some(r)) => stack.push((&n, r.clone())),
+                    (Forward(n), Some(r)) => stack.push((n, r.clone())),
                     _ => {}
                 }
 
@@ -171,7 +170,7 @@ impl Elves {
 
             match &w.fallback {
                 Accept => valid_params.push(params.clone()),
-                Forward(n) => stack.push((&n, params.clone())),
+                Forward(n) => stack.push((n, params.clone())),
                 Reject => {} // don't care
             }
         }
@@ -204,7 +203,9 @@ impl ParameterRange {
                 let mut outside = self.clone();
 
                 inside.ranges.insert(condition.field, value + 1..range.end);
-                outside.ranges.insert(condition.field, range.start..value + 1);
+                outside
+                    .ranges
+                    .insert(condition.field, range.start..value + 1);
 
                 (Some(inside), Some(outside))
             }
@@ -226,12 +227,12 @@ lazy_static! {
 }
 
 impl FromStr for Workflow {
-    type Err = ParseError;
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cap = WORKFLOW_REGEX
             .captures(s)
-            .ok_or(ParseError::Workflow(s.to_owned()))?;
+            .ok_or(ParseErrorKind::Invalid("Invalid workflow".to_owned()))?;
 
         Ok(Workflow {
             name: cap[1].to_owned(),
@@ -248,12 +249,12 @@ impl FromStr for Workflow {
 }
 
 impl FromStr for Condition {
-    type Err = ParseError;
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cap = CONDITION_REGEX
             .captures(s)
-            .ok_or(ParseError::Condition(s.to_owned()))?;
+            .ok_or(ParseErrorKind::Invalid(format!("Invalid condition: {s}")))?;
 
         Ok(Condition {
             field: cap[1].chars().next().unwrap(),
@@ -262,14 +263,14 @@ impl FromStr for Condition {
                 '>' => Greater,
                 _ => unreachable!("regex guard should catch this"),
             },
-            value: cap[3].parse::<u64>().unwrap(),
+            value: number(&cap[3])?,
             decision: cap[4].parse::<Decision>()?,
         })
     }
 }
 
 impl FromStr for Decision {
-    type Err = ParseError;
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
@@ -281,39 +282,25 @@ impl FromStr for Decision {
 }
 
 impl FromStr for Part {
-    type Err = ParseError;
+    type Err = ParseErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cap = PART_REGEX
             .captures(s)
-            .ok_or(ParseError::Part(s.to_owned()))?;
+            .ok_or(ParseErrorKind::Invalid("Invalid part".to_owned()))?;
 
         Ok(Part {
-            x: cap[1].parse::<u64>().unwrap(),
-            m: cap[2].parse::<u64>().unwrap(),
-            a: cap[3].parse::<u64>().unwrap(),
-            s: cap[4].parse::<u64>().unwrap(),
+            x: number(&cap[1])?,
+            m: number(&cap[2])?,
+            a: number(&cap[3])?,
+            s: number(&cap[4])?,
         })
     }
 }
 
-#[derive(Debug)]
-pub enum ParseError {
-    IO(io::Error),
-    Workflow(String),
-    Condition(String),
-    Part(String),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::IO(e) => write!(f, "Could not read file: {e}"),
-            ParseError::Workflow(s) => write!(f, "Invalid workflow: {s}"),
-            ParseError::Condition(s) => write!(f, "Invalid condition: {s}"),
-            ParseError::Part(s) => write!(f, "Invalid part: {s}"),
-        }
-    }
+fn number(s: &str) -> Result<u64, ParseErrorKind> {
+    s.parse()
+        .map_err(|_| ParseErrorKind::Invalid(format!("Invalid number: {s}")))
 }
 
 #[derive(Debug)]
@@ -1,13 +1,14 @@
+use aoc_common::{read_lines, Error, ParseError, ParseErrorKind};
 use std::collections::{HashMap, VecDeque};
-use std::fmt::{Display, Formatter};
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 
+#[derive(Clone)]
 pub struct System {
     messages: VecDeque<Message>,
     modules: HashMap<String, Module>,
 }
 
+#[derive(Clone)]
 pub struct Message {
     pub src: String,
     pub dst: String,
@@ -71,18 +72,20 @@ impl System {
         self.run()
     }
 
-    pub fn from_stream(r: impl Read) -> Result<Self, ParseError> {
-        let reader = BufReader::new(r);
+    pub fn from_stream(r: impl Read) -> Result<Self, Error> {
         let mut broadcaster_defined = false;
 
-        let mut modules: HashMap<String, Module> = reader
-            .lines()
-            .map(|l| {
-                let line = l.map_err(ParseError::IO)?;
+        let mut modules: HashMap<String, Module> = read_lines(r)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let located = |k| Error::from(ParseError::new(k).at_line(i, &line));
 
-                let split: Vec<_> = line.split("->").map(|s| s.trim().to_owned()).collect();
-                let first = split[0].to_owned();
-                let dst = split[1].split(',').map(|s| s.trim().to_owned()).collect();
+                let Some((first, dst)) = line.split_once("->") else {
+                    return Err(located(ParseErrorKind::Missing("\"->\"".to_owned())));
+                };
+                let first = first.trim().to_owned();
+                let dst = dst.split(',').map(|s| s.trim().to_owned()).collect();
 
                 match first.as_str() {
                     "broadcaster" if !broadcaster_defined => {
@@ -111,14 +114,21 @@ impl System {
                             }),
                         ))
                     }
-                    "broadcaster" if broadcaster_defined => Err(ParseError::MultipleBroadcasters),
-                    _ => Err(ParseError::UnknownModule(first.to_owned())),
+                    "broadcaster" if broadcaster_defined => Err(located(ParseErrorKind::Invalid(
+                        "Multiple broadcaster definitions given".to_owned(),
+                    ))),
+                    _ => Err(located(ParseErrorKind::Invalid(format!(
+                        "Unknown module type {first} was given"
+                    )))),
                 }
             })
             .collect::<Result<_, _>>()?;
 
-        if modules.get("broadcaster").is_none() {
-            return Err(ParseError::NoBroadcaster);
+        if !modules.contains_key("broadcaster") {
+            return Err(ParseError::new(ParseErrorKind::Missing(
+                "broadcaster definition".to_owned(),
+            ))
+            .into());
         }
 
         let ms: Vec<Module> = modules.values().cloned().collect();
@@ -168,7 +178,7 @@ impl System {
 
             let new_messages = dst.receive(&msg.src, msg.pulse);
 
-            self.messages.extend(new_messages.into_iter());
+            self.messages.extend(new_messages);
             messages.push(msg);
         }
 
@@ -223,27 +233,6 @@ impl Communicate for Module {
     }
 }
 
-#[derive(Debug)]
-pub enum ParseError {
-    IO(io::Error),
-    NoBroadcaster,
-    MultipleBroadcasters,
-    UnknownModule(String),
-}
-
-impl Display for ParseError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::IO(e) => write!(f, "Could not read file: {e}"),
-            ParseError::NoBroadcaster => write!(f, "No broadcaster definition given."),
-            ParseError::MultipleBroadcasters => {
-                write!(f, "Multiple broadcaster definitions given.")
-            }
-            ParseError::UnknownModule(s) => write!(f, "Unknown module type {s} was given."),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
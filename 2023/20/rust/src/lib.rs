@@ -0,0 +1,54 @@
+pub mod comms;
+
+use aoc_common::{Error, Solution};
+use comms::System;
+use std::io::Read;
+use tracing::trace;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 20;
+
+    type Input = System;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error> {
+        System::from_stream(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+        let mut system = input.clone();
+
+        let (low, high) =
+            (0..1000)
+                .flat_map(|_| system.push_button())
+                .fold((0, 0), |(low, high), m| {
+                    if m.pulse {
+                        (low, high + 1)
+                    } else {
+                        (low + 1, high)
+                    }
+                });
+
+        Ok(low * high)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+        let mut system = input.clone();
+
+        let mut push_count = 1;
+        while !system
+            .push_button()
+            .iter()
+            .any(|m| m.dst == "rx" && !m.pulse)
+        {
+            trace!(push_count, "no low pulse to rx yet");
+            push_count += 1;
+        }
+
+        Ok(push_count)
+    }
+}
@@ -0,0 +1,364 @@
+use crate::Error;
+use aoc_common::{Format, Part, Puzzle};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub struct BenchOptions {
+    pub runs: usize,
+    pub baseline: Option<String>,
+    pub save: Option<String>,
+    pub threshold: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            runs: 10,
+            baseline: None,
+            save: None,
+            threshold: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl From<Part> for Phase {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => Phase::Part1,
+            Part::Two => Phase::Part2,
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse "),
+            Phase::Part1 => write!(f, "part 1"),
+            Phase::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+fn one() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    /// Worker threads the phase ran on, `0` for every core.
+    #[serde(default = "one")]
+    pub threads: usize,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    fn from_samples(
+        year: u16,
+        day: u8,
+        phase: Phase,
+        threads: usize,
+        samples: &mut [Duration],
+    ) -> Self {
+        samples.sort();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        Stats {
+            year,
+            day,
+            phase,
+            threads,
+            runs: n,
+            min_ns: samples[0].as_nanos() as u64,
+            median_ns: median.as_nanos() as u64,
+            max_ns: samples[n - 1].as_nanos() as u64,
+        }
+    }
+
+    fn same_phase(&self, other: &Stats) -> bool {
+        (self.year, self.day, self.phase, self.threads)
+            == (other.year, other.day, other.phase, other.threads)
+    }
+
+    /// Relative change of the median against a matching baseline entry, in percent.
+    fn change(&self, baseline: &[Stats]) -> Option<(u64, f64)> {
+        let b = baseline.iter().find(|b| self.same_phase(b))?;
+        let change = 100.0 * (self.median_ns as f64 - b.median_ns as f64) / b.median_ns as f64;
+
+        Some((b.median_ns, change))
+    }
+
+    /// How many times faster the median is than the one of the same phase run serially.
+    fn speedup(&self, serial: &[Stats]) -> Option<(u64, f64)> {
+        let s = serial.iter().find(|s| {
+            s.threads == 1 && (self.year, self.day, self.phase) == (s.year, s.day, s.phase)
+        })?;
+
+        Some((
+            s.median_ns,
+            s.median_ns as f64 / self.median_ns.max(1) as f64,
+        ))
+    }
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    #[serde(flatten)]
+    stats: &'a Stats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    baseline_median_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    change_percent: Option<f64>,
+    regression: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    serial_median_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speedup: Option<f64>,
+}
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = self.stats;
+
+        write!(
+            f,
+            "{} day {:02} {}: min {:?}, median {:?}, max {:?} ({} runs)",
+            s.year,
+            s.day,
+            s.phase,
+            Duration::from_nanos(s.min_ns),
+            Duration::from_nanos(s.median_ns),
+            Duration::from_nanos(s.max_ns),
+            s.runs
+        )?;
+
+        if let Some(speedup) = self.speedup {
+            match s.threads {
+                0 => write!(f, " [{speedup:.2}x over serial on every core]")?,
+                n => write!(f, " [{speedup:.2}x over serial on {n} threads]")?,
+            }
+        }
+
+        if let Some(change) = self.change_percent {
+            write!(f, " [{change:+.1}% vs baseline")?;
+            if self.regression {
+                write!(f, ", REGRESSION")?;
+            }
+            write!(f, "]")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn time<T>(
+    runs: usize,
+    mut f: impl FnMut() -> Result<T, Error>,
+) -> Result<(T, Vec<Duration>), Error> {
+    let mut samples = Vec::with_capacity(runs);
+    let mut last = None;
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        last = Some(f()?);
+        samples.push(start.elapsed());
+    }
+
+    Ok((last.unwrap(), samples))
+}
+
+pub fn measure(
+    puzzle: Puzzle,
+    input: &[u8],
+    parts: &[Part],
+    runs: usize,
+    threads: usize,
+) -> Result<Vec<Stats>, Error> {
+    let (parsed, mut samples) = time(runs, || Ok(puzzle.parse(input)?))?;
+    let mut stats = vec![Stats::from_samples(
+        puzzle.year,
+        puzzle.day,
+        Phase::Parse,
+        threads,
+        &mut samples,
+    )];
+
+    for &part in parts {
+        let (_, mut samples) = time(runs, || Ok(parsed.solve(part)?))?;
+        stats.push(Stats::from_samples(
+            puzzle.year,
+            puzzle.day,
+            part.into(),
+            threads,
+            &mut samples,
+        ));
+    }
+
+    Ok(stats)
+}
+
+fn load_baseline(path: impl AsRef<Path>) -> Result<Vec<Stats>, Error> {
+    let s = fs::read_to_string(path)?;
+    serde_json::from_str(&s).map_err(|e| Error::Baseline(e.to_string()))
+}
+
+fn save_baseline(path: impl AsRef<Path>, stats: &[Stats]) -> Result<(), Error> {
+    let mut baseline = if path.as_ref().exists() {
+        load_baseline(&path)?
+    } else {
+        vec![]
+    };
+
+    baseline.retain(|b| !stats.iter().any(|s| s.same_phase(b)));
+    baseline.extend(stats.iter().cloned());
+
+    let s = serde_json::to_string_pretty(&baseline).map_err(|e| Error::Baseline(e.to_string()))?;
+    Ok(fs::write(path, s)?)
+}
+
+/// Prints the stats of every phase, along with the speedup over the matching `serial` stats, and
+/// returns how many of them regressed against the baseline.
+pub fn report(
+    stats: &[Stats],
+    serial: &[Stats],
+    opts: &BenchOptions,
+    format: Format,
+) -> Result<usize, Error> {
+    let baseline = match &opts.baseline {
+        Some(path) => load_baseline(path)?,
+        None => vec![],
+    };
+
+    let mut regressions = 0;
+
+    for s in stats {
+        let change = s.change(&baseline);
+        let regression = change.is_some_and(|(_, c)| c > opts.threshold);
+
+        if regression {
+            regressions += 1;
+        }
+
+        let speedup = s.speedup(serial);
+
+        let report = Report {
+            stats: s,
+            baseline_median_ns: change.map(|(b, _)| b),
+            change_percent: change.map(|(_, c)| c),
+            regression,
+            serial_median_ns: speedup.map(|(m, _)| m),
+            speedup: speedup.map(|(_, x)| x),
+        };
+
+        match format {
+            Format::Text => println!("{report}"),
+            Format::Json => println!("{}", serde_json::to_string(&report).unwrap()),
+        }
+    }
+
+    if let Some(path) = &opts.save {
+        save_baseline(path, stats)?;
+    }
+
+    Ok(regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn stats(phase: Phase, median_ns: u64) -> Stats {
+        Stats {
+            year: 2023,
+            day: 5,
+            phase,
+            threads: 1,
+            runs: 1,
+            min_ns: median_ns,
+            median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[rstest]
+    #[case(vec![30, 10, 20], (10, 20, 30))]
+    #[case(vec![40, 10, 30, 20], (10, 25, 40))]
+    #[case(vec![70], (70, 70, 70))]
+    fn min_median_max(#[case] samples: Vec<u64>, #[case] expected: (u64, u64, u64)) {
+        let mut samples: Vec<_> = samples.into_iter().map(Duration::from_nanos).collect();
+
+        let s = Stats::from_samples(2023, 5, Phase::Parse, 1, &mut samples);
+
+        assert_eq!(expected, (s.min_ns, s.median_ns, s.max_ns));
+    }
+
+    #[test]
+    fn baseline_change() {
+        let baseline = vec![stats(Phase::Parse, 100), stats(Phase::Part1, 200)];
+
+        assert_eq!(
+            Some((100, 25.0)),
+            stats(Phase::Parse, 125).change(&baseline)
+        );
+        assert_eq!(
+            Some((200, -50.0)),
+            stats(Phase::Part1, 100).change(&baseline)
+        );
+        assert_eq!(None, stats(Phase::Part2, 100).change(&baseline));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let s = vec![stats(Phase::Parse, 100), stats(Phase::Part2, 300)];
+        let json = serde_json::to_string(&s).unwrap();
+
+        assert!(json.contains(r#""phase":"part2""#));
+        assert_eq!(s, serde_json::from_str::<Vec<Stats>>(&json).unwrap());
+    }
+
+    #[test]
+    fn speedup_over_serial() {
+        let serial = vec![stats(Phase::Part1, 300), stats(Phase::Part2, 100)];
+        let parallel = Stats {
+            threads: 4,
+            ..stats(Phase::Part1, 100)
+        };
+
+        assert_eq!(Some((300, 3.0)), parallel.speedup(&serial));
+        assert_eq!(None, parallel.change(&serial));
+    }
+
+    #[test]
+    fn baseline_without_threads() {
+        let json = r#"[{"year":2023,"day":5,"phase":"parse","runs":1,"min_ns":1,"median_ns":1,"max_ns":1}]"#;
+
+        assert_eq!(
+            1,
+            serde_json::from_str::<Vec<Stats>>(json).unwrap()[0].threads
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Puzzle examples kept as data files in `<year>/<day>/examples`: every `<name>.input` is checked
+//! against the answers in `<name>.part1` and `<name>.part2`, whichever of them exist.
+
+use aoc_common::{Part, Puzzle};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub(crate) struct Example {
+    name: String,
+    pub(crate) input: PathBuf,
+    expected: Vec<(Part, String)>,
+}
+
+pub(crate) fn examples_dir(puzzle: &Puzzle) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(puzzle.year.to_string())
+        .join(format!("{:02}", puzzle.day))
+        .join("examples")
+}
+
+pub(crate) fn discover(dir: &Path) -> io::Result<Vec<Example>> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut inputs: Vec<_> = fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    inputs.retain(|p| p.extension().is_some_and(|e| e == "input"));
+    inputs.sort();
+
+    inputs
+        .into_iter()
+        .map(|input| {
+            let mut expected = vec![];
+
+            for part in Part::BOTH {
+                let answer = input.with_extension(format!("part{part}"));
+                if answer.is_file() {
+                    expected.push((part, fs::read_to_string(answer)?.trim().to_owned()));
+                }
+            }
+
+            Ok(Example {
+                name: input.file_stem().unwrap().to_string_lossy().into_owned(),
+                input,
+                expected,
+            })
+        })
+        .collect()
+}
+
+impl Example {
+    /// Describes every part whose answer does not match the expected one, after passing the input
+    /// through `convert`.
+    fn check(&self, puzzle: &Puzzle, convert: fn(&str) -> String) -> Vec<String> {
+        let label = format!("{} day {:02} {}", puzzle.year, puzzle.day, self.name);
+
+        let parsed = match fs::read_to_string(&self.input)
+            .map_err(aoc_common::Error::from)
+            .and_then(|s| puzzle.parse(convert(&s).as_bytes()))
+        {
+            Ok(parsed) => parsed,
+            Err(e) => return vec![format!("{label}: {e}")],
+        };
+
+        self.expected
+            .iter()
+            .filter_map(|(part, expected)| match parsed.solve(*part) {
+                Ok(answer) if answer.to_string() == *expected => None,
+                Ok(answer) => Some(format!(
+                    "{label} part {part}: expected {expected}, got {answer}"
+                )),
+                Err(e) => Some(format!("{label} part {part}: {e}")),
+            })
+            .collect()
+    }
+}
+
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::as_is(str::to_owned)]
+    #[case::crlf(|s: &str| s.replace('\n', "\r\n"))]
+    #[case::no_trailing_newline(|s: &str| s.trim_end().to_owned())]
+    #[case::bare_cr_at_end(|s: &str| format!("{}\r", s.trim_end()))]
+    fn all_examples(#[case] convert: fn(&str) -> String) {
+        let mut checked = 0;
+        let mut failures = vec![];
+
+        for puzzle in crate::y2023::puzzles() {
+            let examples = discover(&examples_dir(&puzzle)).unwrap();
+
+            for example in examples {
+                checked += example.expected.len();
+                failures.extend(example.check(&puzzle, convert));
+            }
+        }
+
+        assert!(checked > 0, "no examples found");
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn discover_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("b.input"), "").unwrap();
+        fs::write(dir.path().join("b.part2"), "2\n").unwrap();
+        fs::write(dir.path().join("a.input"), "").unwrap();
+        fs::write(dir.path().join("a.part1"), " 1 \n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let examples = discover(dir.path()).unwrap();
+
+        assert_eq!(
+            vec!["a", "b"],
+            examples.iter().map(|e| e.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![(Part::One, "1".to_owned())], examples[0].expected);
+        assert_eq!(vec![(Part::Two, "2".to_owned())], examples[1].expected);
+    }
+}
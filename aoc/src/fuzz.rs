@@ -0,0 +1,133 @@
+//! Feeds random input and mutated examples to every day's parser, which has to reject malformed
+//! input with an error instead of panicking.
+
+use crate::examples::{discover, examples_dir};
+use crate::y2023;
+use aoc_common::Puzzle;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use rstest::rstest;
+use std::fs;
+use std::sync::OnceLock;
+
+/// Characters that appear in the puzzle inputs, so that random input gets past the first checks,
+/// plus a few non-ASCII ones.
+const ALPHABET: &str =
+    "0123456789 abcdefghjklmnrswxyzABCDEFGHJKLRSUXZ\n\r\t:;,.=-+<>{}()#|/\\%&^vO?*é½٣";
+
+/// Every example input, paired with the index of its puzzle in [`y2023::puzzles`].
+fn seeds() -> &'static [(usize, String)] {
+    static SEEDS: OnceLock<Vec<(usize, String)>> = OnceLock::new();
+
+    SEEDS.get_or_init(|| {
+        y2023::puzzles()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, puzzle)| {
+                discover(&examples_dir(puzzle))
+                    .unwrap()
+                    .into_iter()
+                    .map(move |e| (i, fs::read_to_string(e.input).unwrap()))
+            })
+            .collect()
+    })
+}
+
+fn puzzle(index: usize) -> Puzzle {
+    y2023::puzzles()[index]
+}
+
+fn day(day: u8) -> Puzzle {
+    y2023::puzzles().into_iter().find(|p| p.day == day).unwrap()
+}
+
+#[derive(Debug, Clone)]
+enum Edit {
+    Delete,
+    Insert(char),
+    Replace(char),
+    /// Inserts a number too large for any of the integer types the days parse into.
+    Overflow,
+    Truncate,
+}
+
+fn symbol() -> impl Strategy<Value = char> {
+    proptest::sample::select(ALPHABET.chars().collect::<Vec<_>>())
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    prop_oneof![
+        3 => Just(Edit::Delete),
+        3 => symbol().prop_map(Edit::Insert),
+        3 => symbol().prop_map(Edit::Replace),
+        1 => Just(Edit::Overflow),
+        1 => Just(Edit::Truncate),
+    ]
+}
+
+fn mutate(seed: &str, edits: &[(usize, Edit)]) -> String {
+    let mut chars: Vec<char> = seed.chars().collect();
+
+    for (at, edit) in edits {
+        let i = at % (chars.len() + 1);
+
+        match edit {
+            Edit::Delete if i < chars.len() => {
+                chars.remove(i);
+            }
+            Edit::Replace(c) if i < chars.len() => chars[i] = *c,
+            Edit::Insert(c) => chars.insert(i, *c),
+            Edit::Overflow => {
+                chars.splice(i..i, "340282366920938463463374607431768211456".chars());
+            }
+            Edit::Truncate => chars.truncate(i),
+            _ => {}
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+proptest! {
+    #[test]
+    fn random_input(day in 0..y2023::puzzles().len(), input in vec(symbol(), 0..200)) {
+        let input: String = input.into_iter().collect();
+
+        let _ = puzzle(day).parse(input.as_bytes());
+    }
+
+    #[test]
+    fn mutated_examples(seed in 0..seeds().len(), edits in vec((any::<usize>(), edit()), 1..6)) {
+        let (day, example) = &seeds()[seed];
+
+        let _ = puzzle(*day).parse(mutate(example, &edits).as_bytes());
+    }
+}
+
+/// Inputs that used to panic, as found by the properties above.
+#[rstest]
+#[case(2, "a")]
+#[case(2, "Game 1: 340282366920938463463374607431768211456 red")]
+#[case(3, "467..340282366920938463463374607431768211456")]
+#[case(4, "Card 340282366920938463463374607431768211456: 1 | 2")]
+#[case(5, "seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2")]
+#[case(
+    5,
+    "seeds: 79 14\n\nseed-to-soil map:\n50 98 340282366920938463463374607431768211456"
+)]
+#[case(5, "seeds: 79 14\n\nseed-to-soil map:\n50 18446744073709551615 2")]
+#[case(15, "rn=")]
+#[case(16, "")]
+#[case(18, "R 340282366920938463463374607431768211456 (#70c710)")]
+#[case(
+    19,
+    "in{a>340282366920938463463374607431768211456:R,A}\n\n{x=1,m=2,a=3,s=4}"
+)]
+#[case(
+    19,
+    "in{a>1:R,A}\n\n{x=340282366920938463463374607431768211456,m=2,a=3,s=4}"
+)]
+#[case(20, "broadcaster")]
+fn rejected(#[case] n: u8, #[case] input: &str) {
+    assert!(day(n).parse(input.as_bytes()).is_err());
+}
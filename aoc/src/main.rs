@@ -0,0 +1,347 @@
+mod bench;
+#[cfg(test)]
+mod examples;
+#[cfg(test)]
+mod fuzz;
+mod verify;
+mod y2023;
+
+use aoc_common::cli::{option_value, thread_count};
+use aoc_common::{init_logging, open, with_threads, Format, InputProvider, Part, Puzzle, Record};
+use bench::BenchOptions;
+use std::fmt::{Display, Formatter};
+use std::io::{BufReader, Read};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+use std::{env, io};
+
+const USAGE: &str = "Usage:
+    aoc run <year> <day> [<input>] [--part <1|2>] [--format <text|json>] [--log <filter>]
+        [--threads <n>]
+    aoc bench <year> <day> [<input>] [--part <1|2>] [--format <text|json>] [--log <filter>]
+        [--threads <n>] [--runs <n>] [--baseline <file>] [--save-baseline <file>]
+        [--threshold <percent>]
+    aoc verify [<year> [<day>]] [--format <text|json>] [--log <filter>] [--threads <n>]
+
+Without <input> the input is read from $AOC_INPUT_DIR/<year>/<day>/input (the current directory
+by default), downloading it first when $AOC_SESSION holds a session token. An <input> of - reads
+it from stdin. `verify` compares the answers with the ones recorded in answers.toml next to each
+input.
+
+Solver diagnostics are off unless --log or $AOC_LOG gives a tracing filter such as
+aoc_2023_05=trace; a bare level like debug applies to the selected day only.
+
+Days with independent work items spread it over --threads workers, 1 by default and 0 for every
+core. With more than one, bench also measures a serial run and reports the speedup over it.";
+
+#[derive(Debug)]
+pub enum Error {
+    Usage(String),
+    IO(io::Error),
+    UnknownDay(u16, u8),
+    Solution(aoc_common::Error),
+    Baseline(String),
+    Regressions(usize),
+    Answers(String),
+    Failures(usize),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<aoc_common::Error> for Error {
+    fn from(e: aoc_common::Error) -> Self {
+        match e {
+            aoc_common::Error::Usage(s) => Error::Usage(s),
+            e => Error::Solution(e),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "Could not read input file: {e}"),
+            Error::UnknownDay(year, day) => write!(f, "No solution for {year} day {day:02}"),
+            Error::Solution(e) => write!(f, "{e}"),
+            Error::Baseline(s) => write!(f, "Could not read baseline: {s}"),
+            Error::Regressions(n) => write!(f, "{n} phase(s) regressed against the baseline"),
+            Error::Answers(s) => write!(f, "Could not read answers: {s}"),
+            Error::Failures(n) => write!(f, "{n} answer(s) did not match the recorded ones"),
+        }
+    }
+}
+
+struct RunArgs {
+    year: u16,
+    day: u8,
+    input: Option<String>,
+    parts: Vec<Part>,
+    format: Format,
+    log: Option<String>,
+    threads: usize,
+    bench: BenchOptions,
+}
+
+impl RunArgs {
+    fn parse(args: &[String], allow_bench: bool) -> Result<Self, Error> {
+        let mut positional = vec![];
+        let mut parts = Part::BOTH.to_vec();
+        let mut format = Format::Text;
+        let mut log = None;
+        let mut threads = 1;
+        let mut bench = BenchOptions::default();
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    let part = option_value(&mut iter, arg)?;
+                    parts = vec![Part::try_from(part.as_str()).map_err(Error::Usage)?];
+                }
+                "--format" | "-f" => {
+                    let f = option_value(&mut iter, arg)?;
+                    format = Format::try_from(f.as_str()).map_err(Error::Usage)?;
+                }
+                "--log" => log = Some(option_value(&mut iter, arg)?.to_owned()),
+                "--threads" | "-j" => threads = thread_count(option_value(&mut iter, arg)?)?,
+                "--runs" | "-n" if allow_bench => {
+                    let runs = option_value(&mut iter, arg)?;
+                    bench.runs = match runs.parse() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(Error::Usage(format!("Invalid run count: {runs}"))),
+                    };
+                }
+                "--baseline" if allow_bench => {
+                    bench.baseline = Some(option_value(&mut iter, arg)?.to_owned());
+                }
+                "--save-baseline" if allow_bench => {
+                    bench.save = Some(option_value(&mut iter, arg)?.to_owned());
+                }
+                "--threshold" if allow_bench => {
+                    let t = option_value(&mut iter, arg)?;
+                    bench.threshold = t
+                        .parse()
+                        .map_err(|_| Error::Usage(format!("Invalid threshold: {t}")))?;
+                }
+                s if s.starts_with('-') && s != "-" => {
+                    return Err(Error::Usage(format!("Unknown option: {s}")));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let (year, day, input) = match positional[..] {
+            [year, day] => (year, day, None),
+            [year, day, input] => (year, day, Some(input.to_owned())),
+            _ => {
+                return Err(Error::Usage(
+                    "Expected a year, a day and an optional input path".to_owned(),
+                ))
+            }
+        };
+
+        Ok(RunArgs {
+            year: year
+                .parse()
+                .map_err(|_| Error::Usage(format!("Invalid year: {year}")))?,
+            day: day
+                .parse()
+                .map_err(|_| Error::Usage(format!("Invalid day: {day}")))?,
+            input,
+            parts,
+            format,
+            log,
+            threads,
+            bench,
+        })
+    }
+
+    fn input_path(&self) -> Result<PathBuf, Error> {
+        match &self.input {
+            Some(path) => Ok(path.into()),
+            None => Ok(InputProvider::from_env().resolve(self.year, self.day)?),
+        }
+    }
+}
+
+struct VerifyArgs {
+    year: Option<u16>,
+    day: Option<u8>,
+    format: Format,
+    log: Option<String>,
+    threads: usize,
+}
+
+impl VerifyArgs {
+    fn parse(args: &[String]) -> Result<Self, Error> {
+        let mut positional = vec![];
+        let mut format = Format::Text;
+        let mut log = None;
+        let mut threads = 1;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--format" | "-f" => {
+                    let f = option_value(&mut iter, arg)?;
+                    format = Format::try_from(f.as_str()).map_err(Error::Usage)?;
+                }
+                "--log" => log = Some(option_value(&mut iter, arg)?.to_owned()),
+                "--threads" | "-j" => threads = thread_count(option_value(&mut iter, arg)?)?,
+                s if s.starts_with('-') => {
+                    return Err(Error::Usage(format!("Unknown option: {s}")));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        if positional.len() > 2 {
+            return Err(Error::Usage("Expected at most a year and a day".to_owned()));
+        }
+
+        let year = positional
+            .first()
+            .map(|y| {
+                y.parse()
+                    .map_err(|_| Error::Usage(format!("Invalid year: {y}")))
+            })
+            .transpose()?;
+        let day = positional
+            .get(1)
+            .map(|d| {
+                d.parse()
+                    .map_err(|_| Error::Usage(format!("Invalid day: {d}")))
+            })
+            .transpose()?;
+
+        Ok(VerifyArgs {
+            year,
+            day,
+            format,
+            log,
+            threads,
+        })
+    }
+}
+
+fn puzzles(year: u16) -> Vec<Puzzle> {
+    match year {
+        2023 => y2023::puzzles(),
+        _ => vec![],
+    }
+}
+
+fn find_puzzle(year: u16, day: u8) -> Result<Puzzle, Error> {
+    puzzles(year)
+        .into_iter()
+        .find(|p| p.day == day)
+        .ok_or(Error::UnknownDay(year, day))
+}
+
+fn run(args: &[String]) -> Result<(), Error> {
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => solve(RunArgs::parse(&args[1..], false)?),
+        Some("bench") => bench(RunArgs::parse(&args[1..], true)?),
+        Some("verify") => verify(VerifyArgs::parse(&args[1..])?),
+        Some(cmd) => Err(Error::Usage(format!("Unknown command: {cmd}"))),
+        None => Err(Error::Usage("No command provided".to_owned())),
+    }
+}
+
+fn solve(args: RunArgs) -> Result<(), Error> {
+    let puzzle = find_puzzle(args.year, args.day)?;
+    init_logging(args.log.as_deref(), Some(&puzzle))?;
+
+    let input = puzzle.parse(BufReader::new(open(&args.input_path()?)?))?;
+
+    with_threads(args.threads, || {
+        for &part in &args.parts {
+            let start = Instant::now();
+            let answer = input.solve(part)?;
+            let elapsed = start.elapsed();
+
+            let record = Record::new(args.year, args.day, part.number(), answer, elapsed);
+            println!("{}", record.render(args.format));
+        }
+
+        Ok(())
+    })?
+}
+
+fn bench(args: RunArgs) -> Result<(), Error> {
+    let puzzle = find_puzzle(args.year, args.day)?;
+    init_logging(args.log.as_deref(), Some(&puzzle))?;
+
+    let mut input = vec![];
+    open(&args.input_path()?)?.read_to_end(&mut input)?;
+
+    let measure = |threads| {
+        with_threads(threads, || {
+            bench::measure(puzzle, &input, &args.parts, args.bench.runs, threads)
+        })?
+    };
+
+    let stats = measure(args.threads)?;
+    let serial = match args.threads {
+        1 => vec![],
+        _ => measure(1)?,
+    };
+
+    match bench::report(&stats, &serial, &args.bench, args.format)? {
+        0 => Ok(()),
+        n => Err(Error::Regressions(n)),
+    }
+}
+
+fn verify(args: VerifyArgs) -> Result<(), Error> {
+    let selected = match (args.year, args.day) {
+        (Some(year), Some(day)) => vec![find_puzzle(year, day)?],
+        (Some(year), None) => puzzles(year),
+        _ => y2023::puzzles(),
+    };
+
+    // with a single day selected, a bare level applies to it like it does for `run`
+    init_logging(
+        args.log.as_deref(),
+        selected.first().filter(|_| selected.len() == 1),
+    )?;
+
+    let provider = InputProvider::from_env();
+
+    let failures = with_threads(args.threads, || {
+        let mut failures = 0;
+
+        for puzzle in selected {
+            let verdicts = verify::verify(&puzzle, &provider)?;
+            failures += verify::report(&verdicts, args.format);
+        }
+
+        Ok::<_, Error>(failures)
+    })??;
+
+    match failures {
+        0 => Ok(()),
+        n => Err(Error::Failures(n)),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            if let Error::Usage(_) = e {
+                eprintln!("{USAGE}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
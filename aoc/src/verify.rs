@@ -0,0 +1,247 @@
+use crate::Error;
+use aoc_common::{Format, InputProvider, Part, Puzzle};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Accepted answers of a day, kept in `answers.toml` next to its input.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+pub struct Answers {
+    part1: Option<Expected>,
+    part2: Option<Expected>,
+}
+
+/// Answers are written as TOML integers where they fit and as strings otherwise.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Expected {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Number(n) => write!(f, "{n}"),
+            Expected::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl Answers {
+    fn load(path: &Path) -> Result<Option<Self>, Error> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        toml::from_str(&fs::read_to_string(path)?)
+            .map(Some)
+            .map_err(|e| Error::Answers(format!("{}: {e}", path.display())))
+    }
+
+    fn expected(&self, part: Part) -> Option<&Expected> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Verdict {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Verdict {
+    fn missing(puzzle: &Puzzle, part: Part, reason: impl Into<String>) -> Self {
+        Verdict {
+            year: puzzle.year,
+            day: puzzle.day,
+            part: part.number(),
+            status: Status::Missing,
+            expected: None,
+            answer: None,
+            elapsed_ns: None,
+            reason: Some(reason.into()),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {:02} part {}: ", self.year, self.day, self.part)?;
+
+        match self.status {
+            Status::Pass => write!(f, "PASS")?,
+            Status::Fail => write!(f, "FAIL")?,
+            Status::Missing => write!(f, "MISSING")?,
+        }
+
+        match (&self.expected, &self.answer, &self.reason) {
+            (Some(expected), Some(answer), _) if self.status == Status::Fail => {
+                write!(f, ", expected {expected}, got {answer}")?
+            }
+            (_, _, Some(reason)) => write!(f, ", {reason}")?,
+            _ => {}
+        }
+
+        if let Some(ns) = self.elapsed_ns {
+            write!(f, " ({:?})", Duration::from_nanos(ns))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn answers_path(provider: &InputProvider, year: u16, day: u8) -> PathBuf {
+    provider.path(year, day).with_file_name("answers.toml")
+}
+
+/// Solves every part that has a recorded answer; parts without one are reported as missing.
+pub fn verify(puzzle: &Puzzle, provider: &InputProvider) -> Result<Vec<Verdict>, Error> {
+    let Some(answers) = Answers::load(&answers_path(provider, puzzle.year, puzzle.day))? else {
+        return Ok(Part::BOTH
+            .iter()
+            .map(|&part| Verdict::missing(puzzle, part, "no answers.toml"))
+            .collect());
+    };
+
+    let input = provider.path(puzzle.year, puzzle.day);
+    if !input.is_file() {
+        return Ok(Part::BOTH
+            .iter()
+            .map(|&part| Verdict::missing(puzzle, part, "no input"))
+            .collect());
+    }
+
+    let parsed = puzzle.parse(BufReader::new(File::open(input)?));
+
+    Ok(Part::BOTH
+        .iter()
+        .map(|&part| {
+            let Some(expected) = answers.expected(part) else {
+                return Verdict::missing(puzzle, part, "no recorded answer");
+            };
+
+            let start = Instant::now();
+            let answer = match &parsed {
+                Ok(parsed) => parsed.solve(part),
+                Err(e) => Err(aoc_common::Error::Solve(e.to_string())),
+            };
+            let elapsed = start.elapsed();
+
+            let (status, answer, reason) = match answer {
+                Ok(a) if a.to_string() == expected.to_string() => {
+                    (Status::Pass, Some(a.to_string()), None)
+                }
+                Ok(a) => (Status::Fail, Some(a.to_string()), None),
+                Err(e) => (Status::Fail, None, Some(e.to_string())),
+            };
+
+            Verdict {
+                year: puzzle.year,
+                day: puzzle.day,
+                part: part.number(),
+                status,
+                expected: Some(expected.to_string()),
+                answer,
+                elapsed_ns: Some(elapsed.as_nanos() as u64),
+                reason,
+            }
+        })
+        .collect())
+}
+
+/// Prints every verdict and returns how many of them failed.
+pub fn report(verdicts: &[Verdict], format: Format) -> usize {
+    for v in verdicts {
+        match format {
+            Format::Text => println!("{v}"),
+            Format::Json => println!("{}", serde_json::to_string(v).unwrap()),
+        }
+    }
+
+    verdicts.iter().filter(|v| v.status == Status::Fail).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("part1 = 142\npart2 = 281\n", Some("142"), Some("281"))]
+    #[case(
+        "part1 = \"18446744073709551615\"\n",
+        Some("18446744073709551615"),
+        None
+    )]
+    #[case("", None, None)]
+    fn parse_answers(#[case] toml: &str, #[case] part1: Option<&str>, #[case] part2: Option<&str>) {
+        let answers: Answers = toml::from_str(toml).unwrap();
+
+        assert_eq!(
+            part1,
+            answers
+                .expected(Part::One)
+                .map(|e| e.to_string())
+                .as_deref()
+        );
+        assert_eq!(
+            part2,
+            answers
+                .expected(Part::Two)
+                .map(|e| e.to_string())
+                .as_deref()
+        );
+    }
+
+    #[test]
+    fn verdicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = InputProvider::new(dir.path());
+        let puzzle = Puzzle::of::<aoc_2023_01::Day01>();
+
+        let input = provider.path(2023, 1);
+        fs::create_dir_all(input.parent().unwrap()).unwrap();
+        fs::write(&input, "1abc2\ntreb7uchet").unwrap();
+
+        let missing = verify(&puzzle, &provider).unwrap();
+        assert!(missing.iter().all(|v| v.status == Status::Missing));
+
+        fs::write(answers_path(&provider, 2023, 1), "part1 = 89\n").unwrap();
+        let verdicts = verify(&puzzle, &provider).unwrap();
+        assert_eq!(Status::Pass, verdicts[0].status);
+        assert_eq!(Status::Missing, verdicts[1].status);
+
+        fs::write(answers_path(&provider, 2023, 1), "part1 = 90\n").unwrap();
+        let verdicts = verify(&puzzle, &provider).unwrap();
+        assert_eq!(Status::Fail, verdicts[0].status);
+        assert_eq!(
+            "2023 day 01 part 1: FAIL, expected 90, got 89",
+            verdicts[0].to_string().split(" (").next().unwrap()
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use aoc_common::Puzzle;
+
+pub fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::of::<aoc_2023_01::Day01>(),
+        Puzzle::of::<aoc_2023_02::Day02>(),
+        Puzzle::of::<aoc_2023_03::Day03>(),
+        Puzzle::of::<aoc_2023_04::Day04>(),
+        Puzzle::of::<aoc_2023_05::Day05>(),
+        Puzzle::of::<aoc_2023_06::Day06>(),
+        Puzzle::of::<aoc_2023_07::Day07>(),
+        Puzzle::of::<aoc_2023_08::Day08>(),
+        Puzzle::of::<aoc_2023_09::Day09>(),
+        Puzzle::of::<aoc_2023_10::Day10>(),
+        Puzzle::of::<aoc_2023_11::Day11>(),
+        Puzzle::of::<aoc_2023_12::Day12>(),
+        Puzzle::of::<aoc_2023_13::Day13>(),
+        Puzzle::of::<aoc_2023_14::Day14>(),
+        Puzzle::of::<aoc_2023_15::Day15>(),
+        Puzzle::of::<aoc_2023_16::Day16>(),
+        Puzzle::of::<aoc_2023_17::Day17>(),
+        Puzzle::of::<aoc_2023_18::Day18>(),
+        Puzzle::of::<aoc_2023_19::Day19>(),
+        Puzzle::of::<aoc_2023_20::Day20>(),
+    ]
+}
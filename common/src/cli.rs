@@ -0,0 +1,163 @@
+use crate::{
+    init_logging, open, with_threads, Error, Format, InputProvider, Part, Puzzle, Record, Solution,
+};
+use std::env;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+
+/// Shared entry point of the day binaries:
+/// `[<input>] [--part <1|2>] [--format <text|json>] [--log <filter>] [--threads <n>]`.
+///
+/// Without an input path the input is resolved through [`InputProvider::from_env`], and `-` reads
+/// it from stdin.
+pub fn run<S: Solution + 'static>() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match solve(Puzzle::of::<S>(), &args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The value following `option`, or a usage error when the arguments end there.
+pub fn option_value<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<&'a String, Error> {
+    iter.next()
+        .ok_or(Error::Usage(format!("Expected a value after {option}")))
+}
+
+pub fn thread_count(s: &str) -> Result<usize, Error> {
+    s.parse()
+        .map_err(|_| Error::Usage(format!("Invalid thread count: {s}")))
+}
+
+/// The shared options, for day binaries that add modes of their own on top of [`run`].
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub path: Option<String>,
+    pub parts: Vec<Part>,
+    pub format: Format,
+    pub log: Option<String>,
+    pub threads: usize,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            path: None,
+            parts: Part::BOTH.to_vec(),
+            format: Format::Text,
+            log: None,
+            threads: 1,
+        }
+    }
+}
+
+impl Args {
+    /// Rejects unknown options, `-` is still taken as the path of stdin.
+    pub fn parse(args: &[String]) -> Result<Args, Error> {
+        let mut parsed = Args::default();
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    let part = option_value(&mut iter, arg)?;
+                    parsed.parts = vec![Part::try_from(part.as_str()).map_err(Error::Usage)?];
+                }
+                "--format" | "-f" => {
+                    let f = option_value(&mut iter, arg)?;
+                    parsed.format = Format::try_from(f.as_str()).map_err(Error::Usage)?;
+                }
+                "--log" => parsed.log = Some(option_value(&mut iter, arg)?.to_owned()),
+                "--threads" | "-j" => parsed.threads = thread_count(option_value(&mut iter, arg)?)?,
+                s if s.starts_with('-') && s != "-" => {
+                    return Err(Error::Usage(format!("Unknown option: {s}")));
+                }
+                _ if parsed.path.is_some() => {
+                    return Err(Error::Usage(format!("Unexpected argument: {arg}")));
+                }
+                _ => parsed.path = Some(arg.clone()),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// The given input path or else the one resolved through [`InputProvider::from_env`].
+    pub fn resolve_path(&self, year: u16, day: u8) -> Result<PathBuf, Error> {
+        match &self.path {
+            Some(p) => Ok(p.into()),
+            None => InputProvider::from_env().resolve(year, day),
+        }
+    }
+}
+
+fn solve(puzzle: Puzzle, args: &[String]) -> Result<(), Error> {
+    let args = Args::parse(args)?;
+
+    init_logging(args.log.as_deref(), Some(&puzzle))?;
+
+    let path = args.resolve_path(puzzle.year, puzzle.day)?;
+
+    let input = puzzle.parse(BufReader::new(open(&path)?))?;
+
+    with_threads(args.threads, || {
+        for &part in &args.parts {
+            let start = Instant::now();
+            let answer = input.solve(part)?;
+            let elapsed = start.elapsed();
+
+            let record = Record::new(puzzle.year, puzzle.day, part.number(), answer, elapsed);
+            println!("{}", record.render(args.format));
+        }
+
+        Ok(())
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn options_around_path() {
+        let parsed = Args::parse(&args("--part 1 input -f json")).unwrap();
+
+        assert_eq!(
+            Args {
+                path: Some("input".to_owned()),
+                parts: vec![Part::One],
+                format: Format::Json,
+                ..Args::default()
+            },
+            parsed
+        );
+        assert_eq!(Some("-"), Args::parse(&args("-")).unwrap().path.as_deref());
+    }
+
+    #[rstest]
+    #[case("input --verbose", "Unknown option: --verbose")]
+    #[case("input --part", "Expected a value after --part")]
+    #[case("--format", "Expected a value after --format")]
+    #[case("input --log", "Expected a value after --log")]
+    #[case("-j", "Expected a value after -j")]
+    #[case("input other", "Unexpected argument: other")]
+    fn invalid(#[case] input: &str, #[case] expected: &str) {
+        let e = Args::parse(&args(input)).unwrap_err();
+
+        assert_eq!(expected, e.to_string());
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::PathBuf;
+
+const SNIPPET_WIDTH: usize = 60;
+
+#[derive(Debug)]
+pub enum Error {
+    Usage(String),
+    IO(io::Error),
+    NotCached(PathBuf),
+    Fetch(String),
+    Parse(ParseError),
+    Solve(String),
+}
+
+impl Error {
+    /// Parse failure without a known location, e.g. wrapping a day specific error.
+    pub fn invalid(e: impl Display) -> Self {
+        Error::Parse(ParseError::invalid(e))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(s) => write!(f, "{s}"),
+            Error::IO(e) => write!(f, "Could not read input: {e}"),
+            Error::NotCached(p) => write!(
+                f,
+                "No input at {}, pass a path or set AOC_SESSION to download it",
+                p.display()
+            ),
+            Error::Fetch(s) => write!(f, "Could not fetch input: {s}"),
+            Error::Parse(e) => write!(f, "Could not parse input: {e}"),
+            Error::Solve(s) => write!(f, "Could not solve puzzle: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    UnknownCharacter(char),
+    UnevenGrid,
+    EmptyInput,
+    Missing(String),
+    Invalid(String),
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::UnknownCharacter(c) => write!(f, "Unknown character {c:?}"),
+            ParseErrorKind::UnevenGrid => write!(f, "Expected even grid"),
+            ParseErrorKind::EmptyInput => write!(f, "Expected lines"),
+            ParseErrorKind::Missing(s) => write!(f, "Missing {s}"),
+            ParseErrorKind::Invalid(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// 1-based position in the input along with the line it points into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub location: Option<Location>,
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind) -> Self {
+        ParseError {
+            kind,
+            location: None,
+        }
+    }
+
+    pub fn invalid(e: impl Display) -> Self {
+        ParseError::new(ParseErrorKind::Invalid(e.to_string()))
+    }
+
+    /// Points the error at a 0-based line and char column of the input.
+    pub fn at(self, line: usize, column: usize, source: &str) -> Self {
+        ParseError {
+            location: Some(Location {
+                line: line + 1,
+                column: column + 1,
+                snippet: source.trim_end_matches('\r').to_owned(),
+            }),
+            ..self
+        }
+    }
+
+    pub fn at_line(self, line: usize, source: &str) -> Self {
+        self.at(line, 0, source)
+    }
+
+    /// Moves the location down by `lines`, for errors raised on a slice of the input.
+    pub fn offset(self, lines: usize) -> Self {
+        ParseError {
+            location: self.location.map(|l| Location {
+                line: l.line + lines,
+                ..l
+            }),
+            ..self
+        }
+    }
+}
+
+impl From<ParseErrorKind> for ParseError {
+    fn from(kind: ParseErrorKind) -> Self {
+        ParseError::new(kind)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        let Some(loc) = &self.location else {
+            return Ok(());
+        };
+
+        write!(f, " at line {}, column {}", loc.line, loc.column)?;
+
+        // keep long lines readable by only showing a window around the column
+        let start = (loc.column - 1).saturating_sub(SNIPPET_WIDTH / 2);
+        let snippet: String = loc
+            .snippet
+            .chars()
+            .skip(start)
+            .take(SNIPPET_WIDTH)
+            .collect();
+        let gutter = " ".repeat(loc.line.to_string().len());
+
+        write!(
+            f,
+            "\n{} | {snippet}\n{gutter} | {}^",
+            loc.line,
+            " ".repeat(loc.column - 1 - start)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_location() {
+        let e = ParseError::new(ParseErrorKind::UnknownCharacter('x')).at(2, 3, "..|x.\r");
+
+        assert_eq!(
+            "Unknown character 'x' at line 3, column 4\n3 | ..|x.\n  |    ^",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn display_long_line() {
+        let line = format!("{}x{}", ".".repeat(100), ".".repeat(100));
+        let e = ParseError::new(ParseErrorKind::UnknownCharacter('x')).at(11, 100, &line);
+
+        let rendered = e.to_string();
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert_eq!("Unknown character 'x' at line 12, column 101", lines[0]);
+        assert_eq!(
+            format!("12 | {}x{}", ".".repeat(30), ".".repeat(29)),
+            lines[1]
+        );
+        assert_eq!(format!("   | {}^", " ".repeat(30)), lines[2]);
+    }
+
+    #[test]
+    fn display_without_location() {
+        let e = Error::from(ParseError::new(ParseErrorKind::EmptyInput));
+
+        assert_eq!("Could not parse input: Expected lines", e.to_string());
+    }
+}
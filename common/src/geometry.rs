@@ -0,0 +1,206 @@
+use crate::ParseErrorKind;
+use std::fmt::{Debug, Formatter};
+
+/// Compass direction on a grid where `y` grows downwards.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All four directions, clockwise starting from north.
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// Unit step as `(dx, dy)`.
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+}
+
+/// Accepts compass letters, up/down/left/right letters and arrows.
+impl TryFrom<char> for Direction {
+    type Error = ParseErrorKind;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'N' | 'U' | '^' => Ok(Direction::North),
+            'E' | 'R' | '>' => Ok(Direction::East),
+            'S' | 'D' | 'v' => Ok(Direction::South),
+            'W' | 'L' | '<' => Ok(Direction::West),
+            _ => Err(ParseErrorKind::UnknownCharacter(c)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Position {
+    pub fn new(x: i64, y: i64) -> Self {
+        Position { x, y }
+    }
+
+    pub fn step(self, dir: Direction) -> Position {
+        self.step_by(dir, 1)
+    }
+
+    pub fn step_by(self, dir: Direction, n: i64) -> Position {
+        let (dx, dy) = dir.offset();
+
+        Position {
+            x: self.x + dx * n,
+            y: self.y + dy * n,
+        }
+    }
+
+    /// Steps only if the result stays within a `width` by `height` area anchored at the origin.
+    pub fn checked_step(self, dir: Direction, width: usize, height: usize) -> Option<Position> {
+        let next = self.step(dir);
+
+        next.is_within(width, height).then_some(next)
+    }
+
+    pub fn is_within(&self, width: usize, height: usize) -> bool {
+        self.x >= 0 && self.y >= 0 && (self.x as usize) < width && (self.y as usize) < height
+    }
+
+    pub fn manhattan(&self, other: &Position) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Debug for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl From<(i64, i64)> for Position {
+    fn from((x, y): (i64, i64)) -> Self {
+        Position { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    fn from((x, y): (usize, usize)) -> Self {
+        Position {
+            x: x as i64,
+            y: y as i64,
+        }
+    }
+}
+
+impl From<Position> for (i64, i64) {
+    fn from(p: Position) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl TryFrom<Position> for (usize, usize) {
+    type Error = Position;
+
+    fn try_from(p: Position) -> Result<Self, Self::Error> {
+        match (usize::try_from(p.x), usize::try_from(p.y)) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(p),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Direction::North, Direction::West, Direction::East, Direction::South)]
+    #[case(Direction::East, Direction::North, Direction::South, Direction::West)]
+    #[case(Direction::South, Direction::East, Direction::West, Direction::North)]
+    #[case(Direction::West, Direction::South, Direction::North, Direction::East)]
+    fn turning(
+        #[case] dir: Direction,
+        #[case] left: Direction,
+        #[case] right: Direction,
+        #[case] reverse: Direction,
+    ) {
+        assert_eq!(left, dir.turn_left());
+        assert_eq!(right, dir.turn_right());
+        assert_eq!(reverse, dir.reverse());
+        assert_eq!(dir, dir.turn_left().turn_right());
+    }
+
+    #[rstest]
+    #[case(Position::new(0, 0), Direction::North, None)]
+    #[case(Position::new(0, 0), Direction::West, None)]
+    #[case(Position::new(0, 0), Direction::East, Some(Position::new(1, 0)))]
+    #[case(Position::new(2, 1), Direction::East, None)]
+    #[case(Position::new(2, 1), Direction::South, None)]
+    #[case(Position::new(2, 1), Direction::North, Some(Position::new(2, 0)))]
+    fn checked_step(
+        #[case] pos: Position,
+        #[case] dir: Direction,
+        #[case] expected: Option<Position>,
+    ) {
+        assert_eq!(expected, pos.checked_step(dir, 3, 2));
+    }
+
+    #[test]
+    fn step_and_distance() {
+        let start = Position::new(1, 1);
+        let end = start.step_by(Direction::South, 3).step(Direction::West);
+
+        assert_eq!(Position::new(0, 4), end);
+        assert_eq!(4, start.manhattan(&end));
+        assert_eq!(Ok((0, 4)), <(usize, usize)>::try_from(end));
+
+        let off = end.step(Direction::West);
+        assert_eq!(Err(off), <(usize, usize)>::try_from(off));
+    }
+}
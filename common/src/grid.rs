@@ -0,0 +1,381 @@
+use crate::{read_lines, Error, ParseError, ParseErrorKind, Position};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::ops::{Index, IndexMut};
+
+/// Dense row-major grid addressed by `(x, y)`, with `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Reads one row per line, mapping every character into a cell.
+    pub fn parse(
+        input: impl Read,
+        f: impl FnMut(char) -> Result<T, ParseErrorKind>,
+    ) -> Result<Self, Error> {
+        Ok(Self::parse_lines(&read_lines(input)?, f)?)
+    }
+
+    pub fn parse_lines(
+        lines: &[impl AsRef<str>],
+        mut f: impl FnMut(char) -> Result<T, ParseErrorKind>,
+    ) -> Result<Self, ParseError> {
+        let first = lines.first().ok_or(ParseErrorKind::EmptyInput)?;
+        let width = first.as_ref().chars().count();
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            let mut len = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                if x == width {
+                    return Err(ParseError::new(ParseErrorKind::UnevenGrid).at(y, x, line));
+                }
+
+                cells.push(f(c).map_err(|k| ParseError::new(k).at(y, x, line))?);
+                len += 1;
+            }
+
+            if len != width {
+                return Err(ParseError::new(ParseErrorKind::UnevenGrid).at(y, len, line));
+            }
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height: lines.len(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: impl Into<Position>) -> bool {
+        pos.into().is_within(self.width, self.height)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Same as [`Grid::get`], but for coordinates that may have walked off the grid.
+    pub fn get_signed(&self, pos: impl Into<Position>) -> Option<&T> {
+        let pos = pos.into();
+
+        if self.contains(pos) {
+            self.get(pos.x as usize, pos.y as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        self.cells
+            .swap(a.1 * self.width + a.0, b.1 * self.width + b.0);
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.len()).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// In-bounds orthogonal neighbours, clockwise starting from the one above.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(x, y, &[(0, -1), (1, 0), (0, 1), (-1, 0)])
+    }
+
+    /// In-bounds neighbours including diagonals, clockwise starting from the one above.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(
+            x,
+            y,
+            &[
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+        )
+    }
+
+    fn offsets(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &[(i64, i64)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width as i64, self.height as i64);
+
+        offsets
+            .iter()
+            .map(move |(dx, dy)| (x as i64 + dx, y as i64 + dy))
+            .filter(move |&(x, y)| x >= 0 && y >= 0 && x < width && y < height)
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut((usize, usize), &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn remap(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> usize) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: (0..width * height)
+                .map(|i| self.cells[source(i % width, i / width)].clone())
+                .collect(),
+            width,
+            height,
+        }
+    }
+
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| x * self.width + y)
+    }
+
+    /// Rotates a quarter turn clockwise, so the left column becomes the top row.
+    pub fn rotated_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| {
+            (self.height - 1 - x) * self.width + y
+        })
+    }
+
+    /// Rotates a quarter turn counter-clockwise, so the top row becomes the left column.
+    pub fn rotated_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| {
+            x * self.width + (self.width - 1 - y)
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(x < self.width, "column {x} out of bounds");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(x < self.width, "column {x} out of bounds");
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        self.get_signed(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut Self::Output {
+        assert!(self.contains(pos), "position {pos:?} out of bounds");
+        &mut self[(pos.x as usize, pos.y as usize)]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn grid(s: &str) -> Grid<char> {
+        Grid::parse(s.as_bytes(), Ok).unwrap()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let g = grid("abc\ndef");
+
+        assert_eq!((3, 2), (g.width(), g.height()));
+        assert_eq!('a', g[(0, 0)]);
+        assert_eq!('f', g[(2, 1)]);
+        assert_eq!(Some(&'d'), g.get_signed(Position::new(0, 1)));
+        assert_eq!(None, g.get_signed(Position::new(-1, 1)));
+        assert_eq!('e', g[Position::new(1, 1)]);
+        assert_eq!(None, g.get(3, 0));
+        assert_eq!("abc\ndef", g.to_string());
+    }
+
+    #[rstest]
+    #[case("ab\nc", 2, 1)]
+    #[case("ab\nabc", 2, 2)]
+    fn parse_uneven(#[case] input: &str, #[case] line: usize, #[case] column: usize) {
+        let lines: Vec<_> = input.lines().collect();
+        let e = Grid::parse_lines(&lines, Ok).unwrap_err();
+
+        assert_eq!(ParseErrorKind::UnevenGrid, e.kind);
+        let loc = e.location.unwrap();
+        assert_eq!((line, column + 1), (loc.line, loc.column));
+    }
+
+    #[test]
+    fn parse_unknown_char() {
+        let lines = ["..", ".x"];
+        let e = Grid::parse_lines(&lines, |c| match c {
+            '.' => Ok(()),
+            _ => Err(ParseErrorKind::UnknownCharacter(c)),
+        })
+        .unwrap_err();
+
+        assert_eq!(ParseErrorKind::UnknownCharacter('x'), e.kind);
+        assert_eq!(2, e.location.unwrap().column);
+    }
+
+    #[test]
+    fn views() {
+        let g = grid("abc\ndef");
+
+        assert_eq!(vec!['a', 'b', 'c'], g.row(0));
+        assert_eq!(vec![&'b', &'e'], g.column(1).collect::<Vec<_>>());
+        assert_eq!(
+            vec!["cf", "be", "ad"],
+            g.columns()
+                .rev()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case(1, 1, vec![(1, 0), (2, 1), (1, 2), (0, 1)])]
+    #[case(0, 0, vec![(1, 0), (0, 1)])]
+    #[case(2, 2, vec![(2, 1), (1, 2)])]
+    fn neighbors4(#[case] x: usize, #[case] y: usize, #[case] expected: Vec<(usize, usize)>) {
+        let g = grid("...\n...\n...");
+
+        assert_eq!(expected, g.neighbors4(x, y).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn neighbors8() {
+        let g = grid("...\n...\n...");
+
+        assert_eq!(8, g.neighbors8(1, 1).count());
+        assert_eq!(
+            vec![(1, 0), (1, 1), (0, 1)],
+            g.neighbors8(0, 0).collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case(Grid::transposed, "ad\nbe\ncf")]
+    #[case(Grid::rotated_cw, "da\neb\nfc")]
+    #[case(Grid::rotated_ccw, "cf\nbe\nad")]
+    fn transforms(#[case] f: fn(&Grid<char>) -> Grid<char>, #[case] expected: &str) {
+        assert_eq!(expected, f(&grid("abc\ndef")).to_string());
+    }
+
+    #[test]
+    fn full_rotation() {
+        let g = grid("abc\ndef");
+
+        assert_eq!(g, g.rotated_cw().rotated_cw().rotated_ccw().rotated_ccw());
+        assert_eq!(g, g.rotated_cw().rotated_cw().rotated_cw().rotated_cw());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::Error;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Downloads puzzle inputs with a session token from `<base url>/<year>/day/<day>/input`.
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    session: String,
+    base_url: String,
+}
+
+impl Fetcher {
+    pub fn new(session: impl Into<String>) -> Self {
+        Fetcher {
+            session: session.into(),
+            base_url: DEFAULT_BASE_URL.to_owned(),
+        }
+    }
+
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Fetcher {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+        let url = format!(
+            "{}/{year}/day/{day}/input",
+            self.base_url.trim_end_matches('/')
+        );
+
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| Error::Fetch(e.to_string()))?;
+
+        Ok(response.into_string()?)
+    }
+}
+
+/// Resolves inputs to `<dir>/<year>/<day>/input`, the same layout the puzzles are checked in with.
+#[derive(Debug, Clone)]
+pub struct InputProvider {
+    dir: PathBuf,
+    fetcher: Option<Fetcher>,
+}
+
+impl InputProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        InputProvider {
+            dir: dir.into(),
+            fetcher: None,
+        }
+    }
+
+    pub fn with_fetcher(self, fetcher: Fetcher) -> Self {
+        InputProvider {
+            fetcher: Some(fetcher),
+            ..self
+        }
+    }
+
+    /// Configured by `AOC_INPUT_DIR` (the working directory by default), fetching only when
+    /// `AOC_SESSION` is set and from `AOC_BASE_URL` if given.
+    pub fn from_env() -> Self {
+        let provider = InputProvider::new(env::var("AOC_INPUT_DIR").unwrap_or(".".to_owned()));
+
+        match env::var("AOC_SESSION") {
+            Ok(session) => {
+                let fetcher = Fetcher::new(session.trim());
+                provider.with_fetcher(match env::var("AOC_BASE_URL") {
+                    Ok(url) => fetcher.with_base_url(url),
+                    Err(_) => fetcher,
+                })
+            }
+            Err(_) => provider,
+        }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("{day:02}"))
+            .join("input")
+    }
+
+    /// Path of the cached input, downloading it first if it is not cached yet.
+    pub fn resolve(&self, year: u16, day: u8) -> Result<PathBuf, Error> {
+        let path = self.path(year, day);
+
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let Some(fetcher) = &self.fetcher else {
+            return Err(Error::NotCached(path));
+        };
+
+        let input = fetcher.fetch(year, day)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // an interrupted write must not look like a cached input
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, &path)?;
+
+        Ok(path)
+    }
+}
+
+/// Opens an input file, or stdin when the path is `-`.
+pub fn open(path: &Path) -> io::Result<Box<dyn Read>> {
+    if path == Path::new("-") {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(File::open(path)?))
+    }
+}
+
+/// Reads every line of an input, with either LF or CRLF line endings stripped.
+pub fn read_lines(input: impl Read) -> Result<Vec<String>, Error> {
+    BufReader::new(input)
+        .lines()
+        .map(|l| {
+            let mut l = l?;
+            // `lines` leaves the carriage return of a last line that has no line feed
+            if l.ends_with('\r') {
+                l.pop();
+            }
+            Ok(l)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Serves every request with the same canned response and records the request lines.
+    fn stand_in(status: &'static str, body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let seen = requests.clone();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let head: Vec<String> = BufReader::new(&stream)
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|l| !l.is_empty())
+                    .collect();
+                seen.lock().unwrap().push(head.join("\n"));
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        (url, requests)
+    }
+
+    #[rstest]
+    #[case("a\nb\n", vec!["a", "b"])]
+    #[case("a\r\nb\r\n", vec!["a", "b"])]
+    #[case("a\r\n\r\nb", vec!["a", "", "b"])]
+    #[case("a\r\nb\r", vec!["a", "b"])]
+    #[case("", vec![])]
+    fn line_endings(#[case] input: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(expected, read_lines(input.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn cached_without_fetcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = InputProvider::new(dir.path());
+
+        let path = provider.path(2023, 5);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "seeds: 1").unwrap();
+
+        assert_eq!(path, provider.resolve(2023, 5).unwrap());
+        assert!(path.ends_with("2023/05/input"));
+    }
+
+    #[test]
+    fn not_cached_without_fetcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let provider = InputProvider::new(dir.path());
+
+        assert!(matches!(
+            provider.resolve(2023, 5),
+            Err(Error::NotCached(p)) if p == provider.path(2023, 5)
+        ));
+    }
+
+    #[test]
+    fn fetches_once() {
+        let (url, requests) = stand_in("200 OK", "seeds: 79 14\n");
+        let dir = tempfile::tempdir().unwrap();
+        let provider =
+            InputProvider::new(dir.path()).with_fetcher(Fetcher::new("secret").with_base_url(url));
+
+        let path = provider.resolve(2023, 5).unwrap();
+        assert_eq!("seeds: 79 14\n", fs::read_to_string(&path).unwrap());
+
+        assert_eq!(path, provider.resolve(2023, 5).unwrap());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(1, requests.len());
+        assert!(requests[0].starts_with("GET /2023/day/5/input "));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn failed_fetch_is_not_cached() {
+        let (url, _) = stand_in("400 Bad Request", "Please log in");
+        let dir = tempfile::tempdir().unwrap();
+        let provider =
+            InputProvider::new(dir.path()).with_fetcher(Fetcher::new("expired").with_base_url(url));
+
+        assert!(matches!(provider.resolve(2023, 5), Err(Error::Fetch(_))));
+        assert!(!provider.path(2023, 5).exists());
+    }
+}
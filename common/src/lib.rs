@@ -0,0 +1,18 @@
+pub mod cli;
+mod error;
+mod geometry;
+mod grid;
+mod input;
+mod logging;
+mod output;
+mod parallel;
+mod solution;
+
+pub use error::{Error, Location, ParseError, ParseErrorKind};
+pub use geometry::{Direction, Position};
+pub use grid::Grid;
+pub use input::{open, read_lines, Fetcher, InputProvider, DEFAULT_BASE_URL};
+pub use logging::{init_logging, LOG_ENV};
+pub use output::{Answer, Format, Record};
+pub use parallel::with_threads;
+pub use solution::{Parsed, Part, Puzzle, Solution};
@@ -0,0 +1,98 @@
+use crate::{Error, Puzzle};
+use std::env;
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// Sends the trace events of the solvers to stderr, filtered by `directives` or else `$AOC_LOG`.
+///
+/// Both take `tracing` filter directives such as `aoc_2023_05=trace`, where a bare level applies
+/// to the crate of `puzzle` only. Nothing is logged if neither is given.
+pub fn init_logging(directives: Option<&str>, puzzle: Option<&Puzzle>) -> Result<(), Error> {
+    let directives = match directives {
+        Some(d) => d.to_owned(),
+        None => env::var(LOG_ENV).unwrap_or_default(),
+    };
+
+    if directives.is_empty() {
+        return Ok(());
+    }
+
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::OFF.into())
+        .parse(scoped(&directives, puzzle))
+        .map_err(|e| Error::Usage(format!("Invalid log filter {directives:?}: {e}")))?;
+
+    // a subscriber set up earlier, e.g. by a test harness, takes precedence
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .try_init();
+
+    Ok(())
+}
+
+fn scoped(directives: &str, puzzle: Option<&Puzzle>) -> String {
+    directives
+        .split(',')
+        .map(|d| match puzzle {
+            Some(p) if d.parse::<LevelFilter>().is_ok() => {
+                format!("aoc_{}_{:02}={d}", p.year, p.day)
+            }
+            _ => d.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Part, Solution};
+    use rstest::rstest;
+    use std::io::Read;
+
+    struct Noop;
+
+    impl Solution for Noop {
+        const YEAR: u16 = 2023;
+        const DAY: u8 = 5;
+
+        type Input = ();
+        type Part1 = u8;
+        type Part2 = u8;
+
+        fn parse(_: impl Read) -> Result<Self::Input, Error> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Result<Self::Part1, Error> {
+            Ok(Part::One.number())
+        }
+
+        fn part2(_: &Self::Input) -> Result<Self::Part2, Error> {
+            Ok(Part::Two.number())
+        }
+    }
+
+    #[rstest]
+    #[case("debug", "aoc_2023_05=debug")]
+    #[case("trace,ureq=warn", "aoc_2023_05=trace,ureq=warn")]
+    #[case("aoc_2023_17=debug", "aoc_2023_17=debug")]
+    fn scoped_to_puzzle(#[case] directives: &str, #[case] expected: &str) {
+        assert_eq!(expected, scoped(directives, Some(&Puzzle::of::<Noop>())));
+    }
+
+    #[test]
+    fn unscoped() {
+        assert_eq!("debug", scoped("debug", None));
+    }
+
+    #[test]
+    fn invalid_filter() {
+        assert!(matches!(
+            init_logging(Some("aoc=loud"), None),
+            Err(Error::Usage(_))
+        ));
+    }
+}
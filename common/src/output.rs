@@ -0,0 +1,133 @@
+use crate::Error;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Answer of a single part, kept as a JSON value so numbers stay numbers in records.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Answer(Value);
+
+impl Answer {
+    pub fn of(answer: impl Serialize) -> Result<Self, Error> {
+        serde_json::to_value(answer)
+            .map(Answer)
+            .map_err(|e| Error::Solve(e.to_string()))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Value::String(s) => write!(f, "{s}"),
+            v => write!(f, "{v}"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl TryFrom<&str> for Format {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed_ns: u64,
+}
+
+impl Record {
+    pub fn new(year: u16, day: u8, part: u8, answer: Answer, elapsed: Duration) -> Self {
+        Record {
+            year,
+            day,
+            part,
+            answer,
+            elapsed_ns: elapsed.as_nanos() as u64,
+        }
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.to_string(),
+            Format::Json => serde_json::to_string(self).unwrap(),
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} day {:02} part {}: {} ({:?})",
+            self.year,
+            self.day,
+            self.part,
+            self.answer,
+            Duration::from_nanos(self.elapsed_ns)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Answer::of(54877u32).unwrap(), "54877")]
+    #[case(Answer::of(-1057i64).unwrap(), "-1057")]
+    #[case(Answer::of("AB,CD").unwrap(), "AB,CD")]
+    fn answer_display(#[case] answer: Answer, #[case] expected: &str) {
+        assert_eq!(expected, answer.to_string());
+    }
+
+    #[test]
+    fn json_record() {
+        let record = Record::new(
+            2023,
+            7,
+            2,
+            Answer::of(13129439557681u64).unwrap(),
+            Duration::from_micros(1500),
+        );
+
+        assert_eq!(
+            r#"{"year":2023,"day":7,"part":2,"answer":13129439557681,"elapsed_ns":1500000}"#,
+            record.render(Format::Json)
+        );
+    }
+
+    #[test]
+    fn text_record() {
+        let record = Record::new(
+            2023,
+            1,
+            1,
+            Answer::of(142u32).unwrap(),
+            Duration::from_micros(1500),
+        );
+
+        assert_eq!(
+            "2023 day 01 part 1: 142 (1.5ms)",
+            record.render(Format::Text)
+        );
+    }
+}
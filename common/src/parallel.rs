@@ -0,0 +1,35 @@
+use crate::Error;
+
+/// Runs `f` on a pool of `threads` workers, which the parallel iterators of the solvers then
+/// spread their work over. One thread runs everything serially and `0` uses every core.
+///
+/// Solvers only parallelize work whose results are combined in an order-independent way, so the
+/// answers do not depend on the thread count.
+pub fn with_threads<R: Send>(threads: usize, f: impl FnOnce() -> R + Send) -> Result<R, Error> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|e| Error::Solve(format!("Could not start {threads} thread(s): {e}")))?;
+
+    Ok(pool.install(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1)]
+    #[case(4)]
+    fn runs_on_pool(#[case] threads: usize) {
+        let (workers, sum) = with_threads(threads, || {
+            let sum: u64 = (1..=1000u64).into_par_iter().sum();
+            (rayon::current_num_threads(), sum)
+        })
+        .unwrap();
+
+        assert_eq!((threads, 500500), (workers, sum));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{Answer, Error};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::marker::PhantomData;
+
+/// A puzzle solution split into a shared parsing step and the two parts.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// Shared with the worker threads of [`crate::with_threads`].
+    type Input: Send + Sync;
+    type Part1: Display + Serialize;
+    type Part2: Display + Serialize;
+
+    fn parse(input: impl Read) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<&str> for Part {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Unknown part: {s}")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+type ParseFn = fn(&mut dyn Read) -> Result<Box<dyn Parsed>, Error>;
+
+/// Type-erased handle to a [`Solution`], so that days can be picked at runtime.
+#[derive(Copy, Clone)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    parse: ParseFn,
+}
+
+/// Parsed input of a [`Puzzle`] with the typed answers erased into an [`Answer`].
+pub trait Parsed: Send + Sync {
+    fn solve(&self, part: Part) -> Result<Answer, Error>;
+}
+
+struct Input<S: Solution>(S::Input, PhantomData<fn() -> S>);
+
+impl<S: Solution> Parsed for Input<S> {
+    fn solve(&self, part: Part) -> Result<Answer, Error> {
+        match part {
+            Part::One => Answer::of(S::part1(&self.0)?),
+            Part::Two => Answer::of(S::part2(&self.0)?),
+        }
+    }
+}
+
+impl Puzzle {
+    pub fn of<S: Solution + 'static>() -> Self {
+        Puzzle {
+            year: S::YEAR,
+            day: S::DAY,
+            parse: |r| {
+                let input = S::parse(r)?;
+                Ok(Box::new(Input::<S>(input, PhantomData)))
+            },
+        }
+    }
+
+    pub fn parse(&self, mut input: impl Read) -> Result<Box<dyn Parsed>, Error> {
+        (self.parse)(&mut input)
+    }
+
+    pub fn solve(&self, input: impl Read, part: Part) -> Result<Answer, Error> {
+        self.parse(input)?.solve(part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = String;
+
+        fn parse(input: impl Read) -> Result<Self::Input, Error> {
+            std::io::BufReader::new(input)
+                .lines()
+                .map(|l| l?.parse().map_err(|_| Error::invalid("NaN")))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Result<Self::Part1, Error> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Self::Part2, Error> {
+            Ok(format!("{} numbers", input.len()))
+        }
+    }
+
+    #[test]
+    fn erased_solve() {
+        let puzzle = Puzzle::of::<Sum>();
+
+        assert_eq!((2000, 1), (puzzle.year, puzzle.day));
+        assert_eq!(
+            "6",
+            puzzle
+                .solve("1\n2\n3".as_bytes(), Part::One)
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "3 numbers",
+            puzzle
+                .solve("1\n2\n3".as_bytes(), Part::Two)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn erased_parse_error() {
+        let puzzle = Puzzle::of::<Sum>();
+
+        assert!(puzzle.parse("1\nx".as_bytes()).is_err());
+    }
+}